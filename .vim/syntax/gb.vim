@@ -8,13 +8,13 @@ if exists("b:current_syntax")
   finish
 endif
 
-syn match directives '#if\|#else\|#endif\|#macro\|#db\|#dw\|#include\|#import'
+syn match directives '#if\|#else\|#endif\|#macro\|#db\|#dw\|#include\|#import\|#rle\|#lz\|#cycles\|#assert\|#print\|#warn\|#error\|#struct\|#ram\|#var\|#enum\|#byte\|#word\|#fill\|#load\|#endload\|#aligned\|#align'
 syn keyword flags C NC Z NZ
 syn match types contained '[&%]'
 
 syn keyword reserved adc add and bit call ccf cp cpl daa dec di ei halt inc jp jr ld ldh ldi ldd ldhl or pop push res ret rl rla rlc rld rr rra rrc rrca rrd rst sbc scf set sla sll sra srl stop sub swap xor reti rlca nop a b c d e h l af bc de hl sp
 
-syn keyword ops MOD AND OR XOR NOT SHL SHR
+syn keyword ops MOD AND OR XOR NOT SHL SHR EQ NE LT LE GT GE
 
 syn match identifiers '\<[a-zA-Z_][a-zA-Z0-9_]*\>'
 
@@ -10,34 +10,17 @@
 ;some game definitons
 
 ;;OAM stuff
-#dw _PLAYER_SPRITE_POS_Y _OAMRAM
-#dw _PLAYER_SPRITE_POS_X _PLAYER_SPRITE_POS_Y + 1
-#dw _PLAYER_SPRITE_INDEX _PLAYER_SPRITE_POS_X + 1
-#dw _PLAYER_SPRITE_ATTR	_PLAYER_SPRITE_INDEX + 1
-
-#dw _ITEM_SPRITE_POS_Y _PLAYER_SPRITE_ATTR + 1
-#dw _ITEM_SPRITE_POS_X _ITEM_SPRITE_POS_Y + 1
-#dw _ITEM_SPRITE_INDEX _ITEM_SPRITE_POS_X + 1
-#dw _ITEM_SPRITE_ATTR _ITEM_SPRITE_INDEX + 1
-
-#dw _SCORE_DIGIT_1_SPRITE_POS_Y _ITEM_SPRITE_ATTR + 1
-#dw _SCORE_DIGIT_1_SPRITE_POS_X _SCORE_DIGIT_1_SPRITE_POS_Y + 1
-#dw _SCORE_DIGIT_1_SPRITE_INDEX _SCORE_DIGIT_1_SPRITE_POS_X + 1
-#dw _SCORE_DIGIT_1_SPRITE_ATTR _SCORE_DIGIT_1_SPRITE_INDEX + 1
-
-#dw _SCORE_DIGIT_2_SPRITE_POS_Y _SCORE_DIGIT_1_SPRITE_ATTR + 1
-#dw _SCORE_DIGIT_2_SPRITE_POS_X _SCORE_DIGIT_2_SPRITE_POS_Y + 1
-#dw _SCORE_DIGIT_2_SPRITE_INDEX _SCORE_DIGIT_2_SPRITE_POS_X + 1
-#dw _SCORE_DIGIT_2_SPRITE_ATTR _SCORE_DIGIT_2_SPRITE_INDEX + 1
-
-#dw _SCORE_DIGIT_3_SPRITE_POS_Y _SCORE_DIGIT_2_SPRITE_ATTR + 1
-#dw _SCORE_DIGIT_3_SPRITE_POS_X _SCORE_DIGIT_3_SPRITE_POS_Y + 1
-#dw _SCORE_DIGIT_3_SPRITE_INDEX _SCORE_DIGIT_3_SPRITE_POS_X + 1
-#dw _SCORE_DIGIT_3_SPRITE_ATTR _SCORE_DIGIT_3_SPRITE_INDEX + 1
+#struct SPRITE POS_Y 1 POS_X 1 INDEX 1 ATTR 1
+
+#ram _OAMRAM
+#var _PLAYER_SPRITE SPRITE
+#var _ITEM_SPRITE SPRITE
+#var _SCORE_DIGIT_1_SPRITE SPRITE
+#var _SCORE_DIGIT_2_SPRITE SPRITE
+#var _SCORE_DIGIT_3_SPRITE SPRITE
 
 ;;numeric constants
-#db _PLAYER_TILE_HORIZONTAL_VALUE 8
-#db _PLAYER_TILE_VERTICAL_VALUE	9
+#enum "_PLAYER_TILE_" 8 HORIZONTAL_VALUE VERTICAL_VALUE
 #dw _PLAYER_SPEED_DELAY_VALUE 9000
 #db _PLAYER_INITIAL_POS_Y 16 + (10 * 8)
 #db _PLAYER_INITIAL_POS_X 8 + (3 * 8)
@@ -57,25 +40,26 @@
 #db _TILE_NUMBERS_OFFSET_MAX &19 ;tile with "9"
 
 ;;ram values
-#dw _JOYPAD_STATE _RAM
-#dw _PLAYER_INDEX_SPRITE _RAM + 1
-#dw _PLAYER_DIR_Y _RAM + 2
-#dw _PLAYER_DIR_X _RAM + 3
-#dw _PLAYER_POS_Y _RAM + 4
-#dw _PLAYER_POS_X _RAM + 5 
-#dw _PLAYER_MIRRORED_Y _RAM + 6 ;mirrored for sprites
-#dw _PLAYER_MIRRORED_X _RAM + 7
-
-#dw _ITEM_POS_Y _RAM + 8
-#dw _ITEM_POS_X _RAM + 9
-#dw _ITEM_PICKED _RAM + 10
+#ram _RAM
+#var _JOYPAD_STATE 1
+#var _PLAYER_INDEX_SPRITE 1
+#var _PLAYER_DIR_Y 1
+#var _PLAYER_DIR_X 1
+#var _PLAYER_POS_Y 1
+#var _PLAYER_POS_X 1
+#var _PLAYER_MIRRORED_Y 1 ;mirrored for sprites
+#var _PLAYER_MIRRORED_X 1
+
+#var _ITEM_POS_Y 1
+#var _ITEM_POS_X 1
+#var _ITEM_PICKED 1
 
 ;from &FF04, Divider Register, updated on every joypad interrupt
-#dw _PSEUDORANDOM_VAL _RAM + 11 
-#dw _SCORE_VAL _RAM + 12
+#var _PSEUDORANDOM_VAL 1
+#var _SCORE_VAL 1
 
-#dw _PLAYER_SEGMENTS_COUNT _RAM + 13 ;limited to 255 segments (8 bits)
-#dw _SEGMENTS_TTL _RAM + 14 ;the rest of the ram, basically
+#var _PLAYER_SEGMENTS_COUNT 1 ;limited to 255 segments (8 bits)
+#var _SEGMENTS_TTL 1 ;the rest of the ram, basically
 
 #db _SHOW_SCREEN_FLAGS LCDCF_ON OR LCDCF_BG8000 OR LCDCF_BG9800 OR LCDCF_BGON OR LCDCF_OBJ8 OR LCDCF_OBJON
 
@@ -0,0 +1,57 @@
+/// Colour of a pixel with 8 bits per channel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+
+    pub const fn grey(v: u8) -> Self {
+        Self{ r: v, g: v, b: v, a: 255 }
+    }
+
+    /// Perceived brightness (ITU-R BT.601).
+    pub const fn luminance(self) -> u8 {
+        ((self.r as u32 * 299 + self.g as u32 * 587 + self.b as u32 * 114) / 1000) as u8
+    }
+
+    /// Pixels that are mostly transparent.
+    pub const fn is_transparent(self) -> bool {
+        self.a < 128
+    }
+
+    /// Parses a colour written as `RRGGBB`, with an optional `#`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let hex = text.strip_prefix('#').unwrap_or(text);
+
+        if hex.len() != 6 {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some(Self{ r: channel(0)?, g: channel(2)?, b: channel(4)?, a: 255 })
+    }
+
+}
+
+impl std::fmt::Display for Rgba {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)?;
+
+        if self.a != 255 {
+            write!(f, " (alpha {})", self.a)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Decoded image, pixels are stored row by row.
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgba>,
+}
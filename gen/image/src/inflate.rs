@@ -0,0 +1,328 @@
+// <https://www.rfc-editor.org/rfc/rfc1950> (zlib)
+// <https://www.rfc-editor.org/rfc/rfc1951> (deflate)
+
+/// Longest Huffman code allowed by deflate.
+const MAX_BITS: usize = 15;
+
+/// Base lengths for length codes 257..285.
+const LEN_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+
+/// Extra bits for length codes 257..285.
+const LEN_EXTRA: [usize; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Base distances for distance codes 0..29.
+const DIST_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+
+/// Extra bits for distance codes 0..29.
+const DIST_EXTRA: [usize; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// Order in which code length code lengths are stored in a dynamic block.
+const CLEN_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompresses a zlib stream.
+pub fn zlib(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if data.len() < 6 {
+        return Err("Compressed data is truncated.");
+    }
+
+    let (cmf, flg) = (data[0], data[1]);
+
+    if cmf & 0x0F != 8 || !((cmf as u16) << 8 | flg as u16).is_multiple_of(31) {
+        return Err("Invalid zlib header.");
+    }
+
+    if flg & 0x20 != 0 {
+        return Err("Preset dictionaries are not supported.");
+    }
+
+    let out = inflate(&data[2..])?;
+
+    let end = data.len();
+    let expected = u32::from_be_bytes([data[end-4], data[end-3], data[end-2], data[end-1]]);
+
+    if adler32(&out) != expected {
+        return Err("Adler-32 checksum mismatch.");
+    }
+
+    Ok(out)
+}
+
+/// Decompresses raw deflate data.
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut bits = Bits{ data, pos: 0, bit: 0 };
+    let mut out = vec![];
+
+    loop {
+        let last = bits.read(1)? == 1;
+
+        match bits.read(2)? {
+            0 => stored(&mut bits, &mut out)?,
+
+            1 => {
+                let (lit, dist) = fixed_tables();
+                codes(&mut bits, &mut out, &lit, &dist)?;
+            }
+
+            2 => {
+                let (lit, dist) = dynamic_tables(&mut bits)?;
+                codes(&mut bits, &mut out, &lit, &dist)?;
+            }
+
+            _ => return Err("Invalid deflate block type."),
+        }
+
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Reads bits from the least significant bit of each byte first.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: usize,
+}
+
+impl<'a> Bits<'a> {
+
+    fn read(&mut self, count: usize) -> Result<usize, &'static str> {
+        let mut value = 0;
+
+        for i in 0..count {
+            let byte = self.data.get(self.pos).ok_or("Compressed data is truncated.")?;
+            value |= (((*byte >> self.bit) & 1) as usize) << i;
+            self.bit += 1;
+
+            if self.bit == 8 {
+                self.bit = 0;
+                self.pos += 1;
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Skips the remaining bits of the current byte.
+    fn align(&mut self) {
+        if self.bit != 0 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+    }
+
+}
+
+/// Canonical Huffman table.
+struct Huffman {
+    /// Number of codes of each length.
+    counts: [u16; MAX_BITS + 1],
+    /// Symbols ordered by code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+
+    fn new(lengths: &[u8]) -> Result<Self, &'static str> {
+        let mut counts = [0u16; MAX_BITS + 1];
+
+        for len in lengths {
+            counts[*len as usize] += 1;
+        }
+
+        // Reject over-subscribed sets of lengths.
+        let mut left = 1isize;
+
+        for count in &counts[1..] {
+            left = (left << 1) - *count as isize;
+
+            if left < 0 {
+                return Err("Invalid Huffman code lengths.");
+            }
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 1];
+
+        for len in 1..MAX_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                symbols[offsets[*len as usize] as usize] = symbol as u16;
+                offsets[*len as usize] += 1;
+            }
+        }
+
+        Ok(Self{ counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<usize, &'static str> {
+        // First code of the current length, and index of its symbol.
+        let mut code = 0;
+        let mut first = 0;
+        let mut index = 0;
+
+        for len in 1..=MAX_BITS {
+            code |= bits.read(1)?;
+            let count = self.counts[len] as usize;
+
+            if code < first + count {
+                return Ok(self.symbols[index + code - first] as usize);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err("Invalid Huffman code.")
+    }
+
+}
+
+fn stored(bits: &mut Bits, out: &mut Vec<u8>) -> Result<(), &'static str> {
+    bits.align();
+
+    let header = bits.data.get(bits.pos..bits.pos + 4).ok_or("Compressed data is truncated.")?;
+    let len = u16::from_le_bytes([header[0], header[1]]);
+    let nlen = u16::from_le_bytes([header[2], header[3]]);
+
+    if len != !nlen {
+        return Err("Stored block length is corrupted.");
+    }
+
+    let start = bits.pos + 4;
+    let end = start + len as usize;
+    let block = bits.data.get(start..end).ok_or("Compressed data is truncated.")?;
+
+    out.extend_from_slice(block);
+    bits.pos = end;
+
+    Ok(())
+}
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+
+    // Fixed lengths are always valid.
+    let lit = Huffman::new(&lengths).unwrap();
+    let dist = Huffman::new(&[5; 30]).unwrap();
+
+    (lit, dist)
+}
+
+fn dynamic_tables(bits: &mut Bits) -> Result<(Huffman, Huffman), &'static str> {
+    let hlit = bits.read(5)? + 257;
+    let hdist = bits.read(5)? + 1;
+    let hclen = bits.read(4)? + 4;
+
+    let mut clen_lengths = [0u8; 19];
+
+    for i in 0..hclen {
+        clen_lengths[CLEN_ORDER[i]] = bits.read(3)? as u8;
+    }
+
+    let clen = Huffman::new(&clen_lengths)?;
+    let mut lengths = vec![];
+
+    while lengths.len() < hlit + hdist {
+        let symbol = clen.decode(bits)?;
+
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+
+            16 => {
+                let prev = *lengths.last().ok_or("Repeat without a previous length.")?;
+                (prev, 3 + bits.read(2)?)
+            }
+
+            17 => (0, 3 + bits.read(3)?),
+            18 => (0, 11 + bits.read(7)?),
+            _ => return Err("Invalid code length symbol."),
+        };
+
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+
+    if lengths.len() != hlit + hdist {
+        return Err("Too many code lengths.");
+    }
+
+    let lit = Huffman::new(&lengths[..hlit])?;
+    let dist = Huffman::new(&lengths[hlit..])?;
+
+    Ok((lit, dist))
+}
+
+fn codes(
+    bits: &mut Bits,
+    out: &mut Vec<u8>,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> Result<(), &'static str> {
+    loop {
+        let symbol = lit.decode(bits)?;
+
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+
+            256 => return Ok(()),
+
+            257..=285 => {
+                let i = symbol - 257;
+                let len = LEN_BASE[i] + bits.read(LEN_EXTRA[i])?;
+
+                let d = dist.decode(bits)?;
+                let base = *DIST_BASE.get(d).ok_or("Invalid distance code.")?;
+                let distance = base + bits.read(DIST_EXTRA[d])?;
+
+                if distance > out.len() {
+                    return Err("Distance is too far back.");
+                }
+
+                // Copies byte by byte, the source can overlap the output.
+                let start = out.len() - distance;
+
+                for i in 0..len {
+                    out.push(out[start + i]);
+                }
+            }
+
+            _ => return Err("Invalid literal/length code."),
+        }
+    }
+}
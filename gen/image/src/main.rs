@@ -25,102 +25,189 @@
     ];
 */
 
-// <https://netpbm.sourceforge.net/doc/ppm.html>
+mod inflate;
+mod image;
+mod palette;
+mod pgm;
+mod png;
+mod tiles;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    image::Image,
+    palette::{ Palette, Misfit },
+    tiles::Tileset,
+};
 
 use std::io::{ Read, Write, BufReader };
 use std::fs::File;
+use std::path::Path;
+
+/// Maximum number of misfit pixels listed in the error output.
+const MISFITS_SHOWN: usize = 16;
+
+/// Parsed command line arguments.
+struct Args {
+    input: String,
+    output: String,
+    palette: Palette,
+    map: Option<String>,
+    attrs: Option<String>,
+    constants: Option<String>,
+}
 
+/// Usage:
+/// image [INPUT] [OUTPUT]
+/// -p \[COLORS\]             Palette, e.g. `000000,555555,AAAAAA,FFFFFF`, optional.
+/// -m \[FILE\]               Removes duplicate tiles and writes the tilemap, optional.
+/// -a \[FILE\]               Also matches flipped tiles and writes CGB attributes, optional.
+/// -c \[FILE\]               Writes the tile count and map dimensions as constants, optional.
 fn main() {
-    let args = std::env::args().collect::<Vec<_>>();
-    assert!(args.len() == 3, "Expect input and output paths as arguments.");
-
-    let in_path = &args[1];
-    let out_path = &args[2];
+    let args = parse_args(&std::env::args().collect::<Vec<_>>()).unwrap_or_else(|e| fail(&e));
 
-    let f = File::open(in_path).expect("Could not open input file.");
+    let f = File::open(&args.input).expect("Could not open input file.");
     let mut reader = BufReader::new(f);
     let mut in_bytes = Vec::new();
     reader.read_to_end(&mut in_bytes).expect("Coul not read input file.");
 
-    assert!(in_bytes[0] == 0x50 && in_bytes[1] == 0x35,
-        "Format not supported, PGM (PPM P5) required.");
+    let image = decode(&in_bytes).unwrap_or_else(|e| fail(&e));
+    let Image{ width: w, height: h, .. } = image;
 
-    let mut b = 2;
-    let mut info = [0, 0, 0];
-    let mut i = 0;
+    check_size(w, h).unwrap_or_else(|e| fail(&e));
 
-    let mut ascii = String::new();
+    let shades = args.palette.map(&image).unwrap_or_else(|misfits| report(&misfits));
+    let tiles = tiles::encode(&shades, w, h);
 
-    loop {
-        b += 1;
+    let tileset = if args.map.is_some() {
+        Tileset::new(&tiles, args.attrs.is_some()).unwrap_or_else(|e| fail(&e))
+    }else {
+        Tileset{ tiles, map: vec![], attrs: None }
+    };
 
-        if matches!(in_bytes[b], 0x20|0x0a) {
-            info[i] = ascii.parse::<usize>().expect("Could not parse ASCII."); 
-            i += 1;
-            ascii.clear();
-        }
+    let out_bytes = tileset.tile_bytes();
+    write(&args.output, &out_bytes);
+    println!("{}B written from {w}x{h} pixels.", out_bytes.len());
 
-        else {
-            ascii.push(in_bytes[b] as char);
-        }
+    if let Some(path) = &args.map {
+        write(path, &tileset.map);
+        println!("{} unique tiles, {}x{} map written.", tileset.tiles.len(), w / 8, h / 8);
+    }
 
-        if i == 3 {
-            break;
-        }
+    if let (Some(path), Some(attrs)) = (&args.attrs, &tileset.attrs) {
+        write(path, attrs);
+    }
 
-        assert!(b < in_bytes.len(), "Invalid PPM header.");
+    if let Some(path) = &args.constants {
+        let text = constants(&args.input, &tileset, w / 8, h / 8);
+        write(path, text.as_bytes());
     }
+}
 
-    let mut palette = vec![];
-    b += 1;
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    if args.len() < 3 {
+        return Err("Expect input and output paths as arguments.".into());
+    }
 
-    for in_byte in &in_bytes[b..] {
-        if !palette.contains(in_byte) {
-            palette.push(*in_byte);
+    let mut result = Args{
+        input: args[1].clone(),
+        output: args[2].clone(),
+        palette: Palette::Luminance,
+        map: None,
+        attrs: None,
+        constants: None,
+    };
+
+    let mut flags = args[3..].iter();
+
+    while let Some(flag) = flags.next() {
+        let value = flags.next().ok_or_else(|| format!("Missing value for {flag}."))?;
+
+        match flag.as_str() {
+            "-p" => result.palette = Palette::parse(value)?,
+            "-m" => result.map = Some(value.clone()),
+            "-a" => result.attrs = Some(value.clone()),
+            "-c" => result.constants = Some(value.clone()),
+            _ => return Err(format!("Unknown argument {flag}.")),
         }
     }
 
-    assert!(palette.len() <= 4, "Color palette is limited to 4 colors.");
-    palette.sort_unstable();
-
-    let [w, h, _] = info;
-    let mut out_bytes = vec![0; (w * h) / 4];
-
-    // For each tile
-    for ty in 0..(h / 8) {
-        for tx in 0..(w / 8) {
-            // Linear tile index
-            let ti = (w / 8) * ty + tx;
-
-            // For each row in the tile
-            for ry in 0..8 {
-                // Output indices
-                let ob = ti * 16 + ry * 2;
-                let oa = ob + 1;
-
-                // For each pixel in the tile
-                for rx in 0..8 {
-                    // Source value
-                    let si = ty * w * 8 + tx * 8 + ry * w + rx;
-                    let src = in_bytes[b + si];
-
-                    // Index of the color in the palette
-                    let pi = palette.iter().position(|pv| *pv == src).unwrap() as u8;
-                    let pa = ((pi & 2) >> 1) << (7 - rx);
-                    let pb = (pi & 1) << (7 - rx);
-
-                    // Write pixel
-                    out_bytes[oa] |= pa;
-                    out_bytes[ob] |= pb;
-                }
-            }
-        }
+    if result.attrs.is_some() && result.map.is_none() {
+        return Err("Attributes (-a) require a tilemap (-m).".into());
     }
 
-    let mut f = File::create(out_path).expect("Could not create output file.");
-    f.write_all(&out_bytes).expect("Could not write output file.");
+    Ok(result)
+}
 
-    println!("{}B written from {w}x{h} pixels.", out_bytes.len());
+/// Picks the decoder from the magic number.
+fn decode(bytes: &[u8]) -> Result<Image, String> {
+    if bytes.starts_with(&png::SIGNATURE) {
+        png::decode(bytes)
+    }else if bytes.starts_with(&pgm::MAGIC) {
+        pgm::decode(bytes)
+    }else {
+        Err("Format not supported, PNG or PGM (PPM P5) required.".into())
+    }
+}
+
+/// Images are cut into whole tiles of 8x8 pixels.
+fn check_size(w: usize, h: usize) -> Result<(), String> {
+    if !w.is_multiple_of(8) || !h.is_multiple_of(8) {
+        return Err(format!("Image dimensions must be multiples of 8, not {w}x{h}."));
+    }
+
+    Ok(())
 }
 
+/// Assembly source defining the sizes of the tileset and the map.
+/// Names are prefixed with the name of the input file, e.g. `BIG_SHORK_TILE_COUNT`.
+fn constants(input: &str, tileset: &Tileset, map_w: usize, map_h: usize) -> String {
+    let file_name = Path::new(input).file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let stem = file_name.split('.').next().unwrap_or("");
 
+    let mut prefix = stem.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() }else{ '_' })
+        .collect::<String>();
+
+    if !prefix.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        prefix.insert_str(0, "IMG_");
+    }
+
+    let tile_count = tileset.tiles.len();
+
+    format!("\
+;Generated by gen/image from {file_name}
+
+#dw {prefix}_TILE_COUNT {tile_count}
+#dw {prefix}_TILES_LEN {}
+#db {prefix}_MAP_W {map_w}
+#db {prefix}_MAP_H {map_h}
+#dw {prefix}_MAP_LEN {}
+", tile_count * 16, map_w * map_h)
+}
+
+fn write(path: &str, bytes: &[u8]) {
+    let mut f = File::create(path).expect("Could not create output file.");
+    f.write_all(bytes).expect("Could not write output file.");
+}
+
+/// Lists the pixels that do not fit the palette and exits.
+fn report(misfits: &[Misfit]) -> ! {
+    eprintln!("{} pixels do not fit the palette:", misfits.len());
+
+    for Misfit{ x, y, color } in misfits.iter().take(MISFITS_SHOWN) {
+        eprintln!("    ({x}, {y})    {color}");
+    }
+
+    if misfits.len() > MISFITS_SHOWN {
+        eprintln!("    ...");
+    }
+
+    std::process::exit(1);
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{msg}");
+    std::process::exit(1);
+}
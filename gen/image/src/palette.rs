@@ -0,0 +1,100 @@
+use crate::image::{ Image, Rgba };
+
+/// How colours are mapped to the four shades of the Gameboy.
+pub enum Palette {
+    /// Shades are chosen by luminance, from darkest (0) to lightest (3).
+    Luminance,
+    /// Each colour in the list is mapped to its index.
+    Colors(Vec<Rgba>),
+}
+
+/// Pixel whose colour has no shade in the palette.
+pub struct Misfit {
+    pub x: usize,
+    pub y: usize,
+    pub color: Rgba,
+}
+
+impl Palette {
+
+    /// Parses a comma-separated list of up to four `RRGGBB` colours.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut colors = vec![];
+
+        for color in text.split(',') {
+            colors.push(Rgba::parse(color.trim())
+                .ok_or_else(|| format!("Invalid colour '{color}', expected RRGGBB."))?);
+        }
+
+        if colors.len() > 4 {
+            return Err("Color palette is limited to 4 colors.".into());
+        }
+
+        Ok(Self::Colors(colors))
+    }
+
+    /// Returns the shade of each pixel, or all the pixels that do not fit the palette.
+    /// Transparent pixels always use shade 0.
+    pub fn map(&self, image: &Image) -> Result<Vec<u8>, Vec<Misfit>> {
+        match self {
+            Self::Luminance => Ok(Self::map_luminance(image)),
+
+            Self::Colors(colors) => {
+                let mut shades = Vec::with_capacity(image.pixels.len());
+                let mut misfits = vec![];
+
+                for (i, pixel) in image.pixels.iter().enumerate() {
+                    if pixel.is_transparent() {
+                        shades.push(0);
+                        continue;
+                    }
+
+                    let opaque = Rgba{ a: 255, ..*pixel };
+
+                    match colors.iter().position(|c| *c == opaque) {
+                        Some(shade) => shades.push(shade as u8),
+
+                        None => misfits.push(Misfit{
+                            x: i % image.width,
+                            y: i / image.width,
+                            color: *pixel,
+                        }),
+                    }
+                }
+
+                if misfits.is_empty() { Ok(shades) }else{ Err(misfits) }
+            }
+        }
+    }
+
+    /// With four levels of luminance or less, each level gets its own shade.
+    /// Otherwise the luminance is split into four equal bands.
+    fn map_luminance(image: &Image) -> Vec<u8> {
+        let mut levels = vec![];
+
+        for pixel in &image.pixels {
+            let luminance = pixel.luminance();
+
+            if !pixel.is_transparent() && !levels.contains(&luminance) {
+                levels.push(luminance);
+            }
+        }
+
+        levels.sort_unstable();
+
+        image.pixels.iter().map(|pixel| {
+            if pixel.is_transparent() {
+                return 0;
+            }
+
+            let luminance = pixel.luminance();
+
+            if levels.len() <= 4 {
+                levels.iter().position(|l| *l == luminance).unwrap() as u8
+            }else {
+                luminance / 64
+            }
+        }).collect()
+    }
+
+}
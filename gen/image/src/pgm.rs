@@ -0,0 +1,52 @@
+// <https://netpbm.sourceforge.net/doc/pgm.html>
+
+use crate::image::{ Image, Rgba };
+
+pub const MAGIC: [u8; 2] = [0x50, 0x35];
+
+/// Decodes a binary greyscale PGM (P5) file.
+pub fn decode(bytes: &[u8]) -> Result<Image, String> {
+    if !bytes.starts_with(&MAGIC) {
+        return Err("Format not supported, PGM (PPM P5) required.".into());
+    }
+
+    let mut b = 2;
+    let mut info = [0, 0, 0];
+    let mut i = 0;
+
+    let mut ascii = String::new();
+
+    loop {
+        b += 1;
+
+        if b >= bytes.len() {
+            return Err("Invalid PPM header.".into());
+        }
+
+        if matches!(bytes[b], 0x20|0x0a) {
+            info[i] = ascii.parse::<usize>().map_err(|_| "Could not parse ASCII.")?;
+            i += 1;
+            ascii.clear();
+        }
+
+        else {
+            ascii.push(bytes[b] as char);
+        }
+
+        if i == 3 {
+            break;
+        }
+    }
+
+    let [width, height, max] = info;
+    b += 1;
+
+    if max > 255 {
+        return Err("16 bits PGM files are not supported.".into());
+    }
+
+    let data = bytes.get(b..b + width * height).ok_or("Image data is truncated.")?;
+    let pixels = data.iter().map(|v| Rgba::grey(*v)).collect();
+
+    Ok(Image{ width, height, pixels })
+}
@@ -0,0 +1,276 @@
+// <https://www.w3.org/TR/png/>
+
+use crate::{
+    inflate,
+    image::{ Image, Rgba },
+};
+
+pub const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Colour types from the IHDR chunk.
+const GREY: u8 = 0;
+const RGB: u8 = 2;
+const INDEXED: u8 = 3;
+const GREY_ALPHA: u8 = 4;
+const RGBA: u8 = 6;
+
+struct Header {
+    width: usize,
+    height: usize,
+    depth: u8,
+    color: u8,
+}
+
+impl Header {
+
+    /// Number of samples per pixel.
+    const fn channels(&self) -> usize {
+        match self.color {
+            RGB => 3,
+            GREY_ALPHA => 2,
+            RGBA => 4,
+            _ => 1,
+        }
+    }
+
+    /// Size of a pixel in bytes, rounded up, as used by the scanline filters.
+    const fn filter_bpp(&self) -> usize {
+        let bits = self.channels() * self.depth as usize;
+        if bits < 8 { 1 }else{ bits / 8 }
+    }
+
+    /// Size of a scanline in bytes, without the filter type byte.
+    const fn stride(&self) -> usize {
+        (self.width * self.channels() * self.depth as usize).div_ceil(8)
+    }
+
+}
+
+/// Decodes a PNG file into RGBA pixels.
+pub fn decode(bytes: &[u8]) -> Result<Image, String> {
+    if !bytes.starts_with(&SIGNATURE) {
+        return Err("Not a PNG file.".into());
+    }
+
+    let mut header = None;
+    let mut palette: Vec<Rgba> = vec![];
+    let mut trns: Vec<u8> = vec![];
+    let mut idat = vec![];
+    let mut pos = SIGNATURE.len();
+
+    loop {
+        let len = read_u32(bytes, pos)? as usize;
+        let ty = bytes.get(pos + 4..pos + 8).ok_or("Truncated chunk.")?;
+        let data = bytes.get(pos + 8..pos + 8 + len).ok_or("Truncated chunk.")?;
+        let crc = read_u32(bytes, pos + 8 + len)?;
+
+        if crc32(&bytes[pos + 4..pos + 8 + len]) != crc {
+            let name = String::from_utf8_lossy(ty);
+            return Err(format!("CRC mismatch in chunk '{name}'."));
+        }
+
+        match ty {
+            b"IHDR" => header = Some(read_header(data)?),
+
+            b"PLTE" => {
+                palette = data.chunks_exact(3)
+                    .map(|c| Rgba{ r: c[0], g: c[1], b: c[2], a: 255 })
+                    .collect();
+            }
+
+            b"tRNS" => trns = data.to_vec(),
+            b"IDAT" => idat.extend_from_slice(data),
+            b"IEND" => break,
+
+            // Ancillary chunks are ignored.
+            _ => {}
+        }
+
+        pos += 12 + len;
+    }
+
+    let header = header.ok_or("Missing IHDR chunk.")?;
+    let mut data = inflate::zlib(&idat)?;
+    unfilter(&header, &mut data)?;
+
+    // Alpha values from tRNS for indexed images.
+    for (color, alpha) in palette.iter_mut().zip(&trns) {
+        color.a = *alpha;
+    }
+
+    let Header{ width, height, depth, color } = header;
+    let stride = header.stride();
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        // Skip the filter type byte.
+        let row = &data[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+
+        for x in 0..width {
+            let pixel = match color {
+                GREY => {
+                    let v = sample(row, x, depth);
+                    let a = if key(&trns, 0) == Some(v) { 0 }else{ 255 };
+                    let v = scale(v, depth);
+                    Rgba{ r: v, g: v, b: v, a }
+                }
+
+                RGB => {
+                    let (r, g, b) = (sample(row, x * 3, depth),
+                        sample(row, x * 3 + 1, depth), sample(row, x * 3 + 2, depth));
+
+                    let transparent = key(&trns, 0) == Some(r)
+                        && key(&trns, 1) == Some(g)
+                        && key(&trns, 2) == Some(b);
+
+                    let a = if transparent { 0 }else{ 255 };
+                    Rgba{ r: scale(r, depth), g: scale(g, depth), b: scale(b, depth), a }
+                }
+
+                INDEXED => {
+                    let i = sample(row, x, depth) as usize;
+                    *palette.get(i).ok_or_else(|| format!("Palette index {i} out of range."))?
+                }
+
+                GREY_ALPHA => {
+                    let v = scale(sample(row, x * 2, depth), depth);
+                    let a = scale(sample(row, x * 2 + 1, depth), depth);
+                    Rgba{ r: v, g: v, b: v, a }
+                }
+
+                RGBA => Rgba{
+                    r: scale(sample(row, x * 4, depth), depth),
+                    g: scale(sample(row, x * 4 + 1, depth), depth),
+                    b: scale(sample(row, x * 4 + 2, depth), depth),
+                    a: scale(sample(row, x * 4 + 3, depth), depth),
+                },
+
+                _ => unreachable!(),
+            };
+
+            pixels.push(pixel);
+        }
+    }
+
+    Ok(Image{ width, height, pixels })
+}
+
+fn read_header(data: &[u8]) -> Result<Header, String> {
+    if data.len() != 13 {
+        return Err("Invalid IHDR chunk.".into());
+    }
+
+    let width = read_u32(data, 0)? as usize;
+    let height = read_u32(data, 4)? as usize;
+    let (depth, color, interlace) = (data[8], data[9], data[12]);
+
+    let valid = match color {
+        GREY => matches!(depth, 1|2|4|8|16),
+        INDEXED => matches!(depth, 1|2|4|8),
+        RGB|GREY_ALPHA|RGBA => matches!(depth, 8|16),
+        _ => false,
+    };
+
+    if !valid {
+        return Err(format!("Unsupported colour type {color} with bit depth {depth}."));
+    }
+
+    if interlace != 0 {
+        return Err("Interlaced images are not supported.".into());
+    }
+
+    Ok(Header{ width, height, depth, color })
+}
+
+/// Reverses the filter of each scanline in place.
+fn unfilter(header: &Header, data: &mut [u8]) -> Result<(), String> {
+    let stride = header.stride();
+    let bpp = header.filter_bpp();
+
+    if data.len() < (stride + 1) * header.height {
+        return Err("Image data is truncated.".into());
+    }
+
+    for y in 0..header.height {
+        let start = y * (stride + 1);
+        let filter = data[start];
+
+        for i in 0..stride {
+            let at = start + 1 + i;
+            let left = if i >= bpp { data[at - bpp] }else{ 0 };
+            let up = if y > 0 { data[at - stride - 1] }else{ 0 };
+            let up_left = if y > 0 && i >= bpp { data[at - stride - 1 - bpp] }else{ 0 };
+
+            let predictor = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(format!("Invalid filter type {filter} on row {y}.")),
+            };
+
+            data[at] = data[at].wrapping_add(predictor);
+        }
+    }
+
+    Ok(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+
+    if pa <= pb && pa <= pc { a }else if pb <= pc { b }else{ c }
+}
+
+/// Reads the sample at index `i` of a scanline, for any bit depth.
+fn sample(row: &[u8], i: usize, depth: u8) -> u16 {
+    match depth {
+        16 => u16::from_be_bytes([row[i * 2], row[i * 2 + 1]]),
+        8 => row[i] as u16,
+
+        _ => {
+            let depth = depth as usize;
+            let bit = i * depth;
+            let shift = 8 - depth - bit % 8;
+            ((row[bit / 8] >> shift) & ((1 << depth) - 1) as u8) as u16
+        }
+    }
+}
+
+/// Scales a sample to 8 bits.
+fn scale(v: u16, depth: u8) -> u8 {
+    match depth {
+        16 => (v >> 8) as u8,
+        8 => v as u8,
+        _ => (v * 255 / ((1 << depth) - 1)) as u8,
+    }
+}
+
+/// Reads a 16 bits value from a tRNS chunk for grey and RGB images.
+fn key(trns: &[u8], i: usize) -> Option<u16> {
+    trns.get(i * 2..i * 2 + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> Result<u32, String> {
+    let b = bytes.get(pos..pos + 4).ok_or("Unexpected end of file.")?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
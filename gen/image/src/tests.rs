@@ -0,0 +1,154 @@
+use crate::{
+    check_size,
+    inflate::{ self, adler32 },
+    image::Rgba,
+    palette::Palette,
+    png::{ self, crc32, SIGNATURE },
+    tiles::{ self, Tile, Tileset, ATTR_X_FLIP, ATTR_Y_FLIP },
+};
+
+/// Wraps data in a zlib stream of one stored block.
+fn stored(data: &[u8]) -> Vec<u8> {
+    let len = data.len() as u16;
+    let mut bytes = vec![0x78, 0x01, 0b001];
+
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes.extend_from_slice(&(!len).to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+/// Writes a PNG file from its chunks.
+fn png(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut bytes = SIGNATURE.to_vec();
+
+    for (ty, data) in chunks {
+        let mut chunk = ty.to_vec();
+        chunk.extend_from_slice(data);
+
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&chunk);
+        bytes.extend_from_slice(&crc32(&chunk).to_be_bytes());
+    }
+
+    bytes
+}
+
+fn header(width: u32, height: u32, depth: u8, color: u8) -> Vec<u8> {
+    let mut data = width.to_be_bytes().to_vec();
+    data.extend_from_slice(&height.to_be_bytes());
+    data.extend_from_slice(&[depth, color, 0, 0, 0]);
+    data
+}
+
+#[test]
+fn inflate_blocks() {
+    assert_eq!(inflate::zlib(&stored(b"stored")).unwrap(), b"stored");
+
+    let fixed = [120, 1, 75, 76, 74, 78, 132, 33, 0, 29, 224, 4, 153];
+    assert_eq!(inflate::zlib(&fixed).unwrap(), b"abcabcabcabc");
+
+    let dynamic = [
+        120, 218, 13, 200, 49, 1, 0, 0, 12, 131, 48, 173, 192, 252, 107,
+        88, 249, 194, 225, 130, 234, 52, 108, 24, 183, 121, 155, 195, 10, 185,
+    ];
+    assert_eq!(inflate::zlib(&dynamic).unwrap(), b"dabbbbaacccdbbcabcbaaaacabba");
+
+    let mut corrupted = stored(b"stored");
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert_eq!(inflate::zlib(&corrupted), Err("Adler-32 checksum mismatch."));
+}
+
+/// The same 2x2 grey image, [10, 30] over [50, 100], with each filter.
+#[test]
+fn png_filters() {
+    let rows: [[u8; 4]; 5] = [
+        [10, 30, 50, 100],
+        [10, 20, 50, 50],
+        [10, 30, 40, 70],
+        [10, 25, 45, 60],
+        [10, 20, 40, 50],
+    ];
+
+    for (filter, row) in rows.iter().enumerate() {
+        let filter = filter as u8;
+        let data = [filter, row[0], row[1], filter, row[2], row[3]];
+        let file = png(&[(b"IHDR", &header(2, 2, 8, 0)), (b"IDAT", &stored(&data)), (b"IEND", &[])]);
+
+        let image = png::decode(&file).unwrap();
+        let pixels = [10, 30, 50, 100].map(Rgba::grey);
+
+        assert_eq!(image.pixels, pixels, "filter {filter}");
+    }
+}
+
+#[test]
+fn png_indexed() {
+    let palette = [255, 0, 0, 0, 255, 0, 0, 0, 255];
+    let data = [0, 0b00_01_10_00];
+
+    let file = png(&[
+        (b"IHDR", &header(3, 1, 2, 3)),
+        (b"PLTE", &palette),
+        (b"tRNS", &[0]),
+        (b"IDAT", &stored(&data)),
+        (b"IEND", &[]),
+    ]);
+
+    let image = png::decode(&file).unwrap();
+
+    assert_eq!(image.pixels, [
+        Rgba{ r: 255, g: 0, b: 0, a: 0 },
+        Rgba{ r: 0, g: 255, b: 0, a: 255 },
+        Rgba{ r: 0, g: 0, b: 255, a: 255 },
+    ]);
+}
+
+#[test]
+fn errors() {
+    assert!(check_size(16, 8).is_ok());
+    assert_eq!(check_size(12, 8), Err("Image dimensions must be multiples of 8, not 12x8.".into()));
+
+    let colors = "000000,555555,AAAAAA,FFFFFF,FF0000";
+    assert_eq!(Palette::parse(colors).err(), Some("Color palette is limited to 4 colors.".into()));
+
+    let data = [0, 0, 255];
+    let file = png(&[(b"IHDR", &header(2, 1, 8, 0)), (b"IDAT", &stored(&data)), (b"IEND", &[])]);
+    let image = png::decode(&file).unwrap();
+
+    let misfits = Palette::parse("000000").unwrap().map(&image).err().unwrap();
+    assert_eq!(misfits.len(), 1);
+    assert_eq!((misfits[0].x, misfits[0].y, misfits[0].color), (1, 0, Rgba::grey(255)));
+}
+
+#[test]
+fn tileset() {
+    let tile: Tile = std::array::from_fn(|i| i as u8 + 1);
+    let other: Tile = [0xFF; 16];
+
+    assert_eq!(tiles::x_flip(&tile)[0], 0b1000_0000);
+    assert_eq!(tiles::y_flip(&tile)[..2], [15, 16]);
+    assert_eq!(tiles::x_flip(&tiles::x_flip(&tile)), tile);
+
+    let flipped = [tile, other, tile, tiles::x_flip(&tile), tiles::y_flip(&tile), tiles::x_flip(&tiles::y_flip(&tile))];
+
+    let dedup = Tileset::new(&flipped, false).unwrap();
+    assert_eq!(dedup.tiles.len(), 5);
+    assert_eq!(dedup.map, [0, 1, 0, 2, 3, 4]);
+    assert_eq!(dedup.attrs, None);
+
+    let matched = Tileset::new(&flipped, true).unwrap();
+    assert_eq!(matched.tiles, [tile, other]);
+    assert_eq!(matched.map, [0, 1, 0, 0, 0, 0]);
+    assert_eq!(matched.attrs, Some(vec![0, 0, 0, ATTR_X_FLIP, ATTR_Y_FLIP, ATTR_X_FLIP | ATTR_Y_FLIP]));
+
+    let unique = (0..=256).map(|i: u16| {
+        let mut tile = [0; 16];
+        tile[..2].copy_from_slice(&i.to_le_bytes());
+        tile
+    }).collect::<Vec<_>>();
+
+    assert!(Tileset::new(&unique[..256], false).is_ok());
+    assert_eq!(Tileset::new(&unique, false).err(), Some("Too many unique tiles, the map is limited to 256.".into()));
+}
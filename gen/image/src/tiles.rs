@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+/// A tile of 8x8 pixels in the 2bpp format of the Gameboy.
+pub type Tile = [u8; 16];
+
+/// CGB attribute bits for flipped tiles.
+pub const ATTR_X_FLIP: u8 = 0b0010_0000;
+pub const ATTR_Y_FLIP: u8 = 0b0100_0000;
+
+/// Encodes the shades of an image into tiles, from left to right and top to bottom.
+pub fn encode(shades: &[u8], w: usize, h: usize) -> Vec<Tile> {
+    let mut tiles = vec![[0; 16]; (w / 8) * (h / 8)];
+
+    // For each tile
+    for ty in 0..(h / 8) {
+        for tx in 0..(w / 8) {
+            // Linear tile index
+            let tile = &mut tiles[(w / 8) * ty + tx];
+
+            // For each row in the tile
+            for ry in 0..8 {
+                // Output indices
+                let ob = ry * 2;
+                let oa = ob + 1;
+
+                // For each pixel in the tile
+                for rx in 0..8 {
+                    // Index of the color in the palette
+                    let si = ty * w * 8 + tx * 8 + ry * w + rx;
+                    let pi = shades[si];
+                    let pa = ((pi & 2) >> 1) << (7 - rx);
+                    let pb = (pi & 1) << (7 - rx);
+
+                    // Write pixel
+                    tile[oa] |= pa;
+                    tile[ob] |= pb;
+                }
+            }
+        }
+    }
+
+    tiles
+}
+
+/// Unique tiles and the map that indexes them.
+pub struct Tileset {
+    pub tiles: Vec<Tile>,
+    pub map: Vec<u8>,
+    /// CGB attributes for each entry of the map, only with flipped-tile matching.
+    pub attrs: Option<Vec<u8>>,
+}
+
+impl Tileset {
+
+    /// Removes duplicate tiles, optionally matching flipped versions of the same tile.
+    pub fn new(tiles: &[Tile], flip: bool) -> Result<Self, String> {
+        let mut unique: Vec<Tile> = vec![];
+        let mut indices = HashMap::new();
+        let mut map = vec![];
+        let mut attrs = vec![];
+
+        for tile in tiles {
+            let variants = if flip {
+                vec![
+                    (*tile, 0),
+                    (x_flip(tile), ATTR_X_FLIP),
+                    (y_flip(tile), ATTR_Y_FLIP),
+                    (x_flip(&y_flip(tile)), ATTR_X_FLIP | ATTR_Y_FLIP),
+                ]
+            }else {
+                vec![(*tile, 0)]
+            };
+
+            // The flipped version of a known tile is displayed with the same flip.
+            let known = variants.iter().find_map(|(variant, attr)| {
+                indices.get(variant).map(|index| (*index, *attr))
+            });
+
+            let (index, attr) = known.unwrap_or_else(|| {
+                indices.insert(*tile, unique.len());
+                unique.push(*tile);
+                (unique.len() - 1, 0)
+            });
+
+            if index > u8::MAX as usize {
+                return Err(format!("Too many unique tiles, the map is limited to {}.", 
+                    u8::MAX as usize + 1));
+            }
+
+            map.push(index as u8);
+            attrs.push(attr);
+        }
+
+        Ok(Self{ tiles: unique, map, attrs: flip.then_some(attrs) })
+    }
+
+    /// Tiles as a continuous stream of bytes.
+    pub fn tile_bytes(&self) -> Vec<u8> {
+        self.tiles.iter().flatten().copied().collect()
+    }
+
+}
+
+/// Mirrors a tile horizontally.
+pub fn x_flip(tile: &Tile) -> Tile {
+    tile.map(u8::reverse_bits)
+}
+
+/// Mirrors a tile vertically.
+pub fn y_flip(tile: &Tile) -> Tile {
+    let mut flipped = [0; 16];
+
+    for row in 0..8 {
+        flipped[row * 2] = tile[(7 - row) * 2];
+        flipped[row * 2 + 1] = tile[(7 - row) * 2 + 1];
+    }
+
+    flipped
+}
@@ -104,7 +104,10 @@ for (instr of INSTRUCTIONS) {
             : `ty(${pascal(arg)})`;
     }).join(', ');
 
-    curOpStr += `${ttt}(${instr.len}, ${instr.code}, vec![${args}])`;
+    // Machine cycles when the condition is met, and when it is not.
+    const [taken, skipped = taken] = instr.cycles.split('/').map(c => c / 4);
+
+    curOpStr += `${ttt}(${instr.len}, ${instr.code}, (${taken}, ${skipped}), vec![${args}])`;
 
     if (instr.cb) {
         cbOpStr += curOpStr;
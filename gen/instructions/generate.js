@@ -0,0 +1,39 @@
+// Runs the scripts outside of a browser, with node:
+//      node generate.js
+// Writes the result to 'src/write/instructions.rs'.
+
+const fs = require('fs');
+const vm = require('vm');
+const path = require('path');
+
+const html = fs.readFileSync(path.join(__dirname, 'opcodes.html'), 'utf8');
+
+/// Element with only what the scripts read.
+const element = (tag, innerHTML, children) => ({ tag, innerHTML, children });
+
+/// Elements of type `tag` directly inside `html`, tables are not nested.
+const elements = (html, tag) => Array.from(
+    html.matchAll(new RegExp(`<${tag}[^>]*>([\\s\\S]*?)</${tag}>`, 'g')),
+    match => match[1],
+);
+
+const tables = elements(html, 'table').map(table => {
+    const rows = elements(table, 'tr').map(tr => 
+        element('tr', tr, elements(tr, 'td').map(td => element('td', td, []))));
+
+    const tbody = element('tbody', table, rows);
+    return { getElementsByTagName: () => [tbody] };
+});
+
+global.document = { querySelectorAll: () => tables };
+global.window = { location: { search: '' } };
+
+// The scripts only log the output.
+console.log = () => {};
+
+for (const file of ['instructions.rs', 'prepare.js', 'format.js']) {
+    vm.runInThisContext(fs.readFileSync(path.join(__dirname, file), 'utf8'), { filename: file });
+}
+
+const output = vm.runInThisContext('output');
+fs.writeFileSync(path.join(__dirname, '../../src/write/instructions.rs'), output);
@@ -6,6 +6,9 @@ use crate::{
     token::read::TokenRef,
 };
 
+/// Length, opcode, cycles and arguments of one form of an instruction.
+pub type Variant = (u8, u8, (u8, u8), Vec<Arg>);
+
 pub fn find(instruction: &TokenRef) -> Option<OpCode> {
     assert_eq!(instruction.ty(), Instruction);
 
@@ -16,16 +19,16 @@ pub fn find(instruction: &TokenRef) -> Option<OpCode> {
 }
 
 #[cfg(test)]
-pub fn get_instruction_info(tty: TokenType) -> Option<(bool, Vec<(u8, u8, Vec<Arg>)>)> {
+pub fn get_instruction_info(tty: TokenType) -> Option<(bool, Vec<Variant>)> {
     _get_instruction_info(tty)
 }
 
 #[cfg(not(test))]
-fn get_instruction_info(tty: TokenType) -> Option<(bool, Vec<(u8, u8, Vec<Arg>)>)> {
+fn get_instruction_info(tty: TokenType) -> Option<(bool, Vec<Variant>)> {
     _get_instruction_info(tty)
 }
 
-fn _get_instruction_info(tty: TokenType) -> Option<(bool, Vec<(u8, u8, Vec<Arg>)>)> {
+fn _get_instruction_info(tty: TokenType) -> Option<(bool, Vec<Variant>)> {
     // {{{ js }}}
 }
 `;
@@ -55,7 +55,13 @@ const INSTRUCTIONS = (() => {
             }
         }
 
-        const [len, cycles] = ln1;
+        let [len, cycles] = ln1;
+
+        // The table lists 16 cycles, 'BIT' only reads from memory.
+        if (name == 'bit' && args[1] == '(HL)') {
+            cycles = '12';
+        }
+
         const flags = lines[2].split(' ');
         
         const instruction = new Instruction(str, cb, code, name, args, len, cycles, flags);
@@ -42,6 +42,11 @@ impl TokenType {
         }
     }
 
+    /// Every word accepted by `get_by_word`.
+    pub const WORDS: &'static [&'static str] = &[
+        //[[words]]
+    ];
+
     /// Is there any type that starts with this prefix character?
     pub const fn has_prefix(prefix: char) -> bool {
         //[[prefixes]]
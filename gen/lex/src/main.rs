@@ -55,6 +55,9 @@ fn build(tree: &Tree) {
     fmt_words(tree, are_words, word_pairs, char_words, &mut fmt);
     apply(&mut result, "get_by_word", &mut fmt, 3);
 
+    fmt_word_list(tree, are_words, word_pairs, char_words, &mut fmt);
+    apply(&mut result, "words", &mut fmt, 2);
+
     fmt_prefixes(tree, prefixes, &mut fmt);
     apply(&mut result, "prefixes", &mut fmt, 3);
 
@@ -178,6 +181,25 @@ fn fmt_words(tree: &Tree, words: &Node, pairs: &Node, chars: &Node, out: &mut St
     }
 }
 
+fn fmt_word_list(tree: &Tree, words: &Node, pairs: &Node, chars: &Node, out: &mut String) {
+    let mut list = vec![];
+
+    for index in &words.children {
+        list.push(tree.nodes[*index].value.to_lowercase());
+    }
+
+    for index in pairs.children.iter().chain(&chars.children) {
+        let child = &tree.nodes[*index];
+        list.push(tree.nodes[child.children[0]].value.clone());
+    }
+
+    for line in list.chunks(8) {
+        let line = line.iter().map(|w| format!("\"{w}\",")).collect::<Vec<_>>().join(" ");
+        out.push_str(&line);
+        out.push('\n');
+    }
+}
+
 fn fmt_prefixes(tree: &Tree, node: &Node, out: &mut String) {
     out.push_str("matches!(prefix, ");
 
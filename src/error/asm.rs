@@ -5,7 +5,12 @@ use crate::{
 };
 
 pub trait AsmMsg: Sized + std::fmt::Debug {
-    fn msg(&self) -> &'static str;
+    fn msg(&self) -> &str;
+
+    /// Values of the error, such as sizes, written after the message.
+    fn detail(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Error when parsing values from the source file.
@@ -16,6 +21,24 @@ pub struct AsmErr<'a, T: AsmMsg> {
     pub source_ctx: SourceCtx,
 }
 
+impl<T: AsmMsg> AsmErr<'_, T> {
+
+    /// Name of the variant, without its values.
+    pub fn code(&self) -> String {
+        let name = format!("{:?}", self.ty);
+        name.split([' ', '(', '{']).next().unwrap_or_default().into()
+    }
+
+    /// Message followed by its detail.
+    pub fn message(&self) -> String {
+        match self.ty.detail() {
+            Some(detail) => format!("{}: {detail}", self.ty.msg()),
+            None => self.ty.msg().into(),
+        }
+    }
+
+}
+
 impl<'a, T: AsmMsg> std::fmt::Display for AsmErr<'a, T> {
 
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -23,8 +46,8 @@ impl<'a, T: AsmMsg> std::fmt::Display for AsmErr<'a, T> {
 
         let mut strip = fmt::strip()
             .debug(&format!("{} ({:?})\n", self.source_ctx, ty)) 
-            .info(&format!("({:?}) ", self.ty))
-            .bold(&format!("{}\n", self.ty.msg()));
+            .info(&format!("({}) ", self.code()))
+            .bold(&format!("{}\n", self.message()));
 
         if let Some(word_start) = self.err_ctx.word_start() {
             let line_a = line.get(..word_start).unwrap();
@@ -84,6 +107,9 @@ pub enum ParseMsg {
     BadAnonMark,
     BadAnonMarkHex,
     ReservedKeyword,
+    BadImport,
+    ImportNotFound,
+    Internal,
 }
 
 impl AsmMsg for ParseMsg {
@@ -112,6 +138,9 @@ impl AsmMsg for ParseMsg {
             BadAnonMarkHex => "Bad anonymous marker's hexadecimal literal",
             BadIdent => "Bad identifier",
             ReservedKeyword => "Identifier cannot be a reserved keyword",
+            BadImport => "Import expected a file path",
+            ImportNotFound => "The file to import could not be read",
+            Internal => "Internal error of the assembler, this is a bug",
         }
     }
 }
@@ -126,6 +155,7 @@ pub enum AstMsg {
     BinaryWithoutLhs,
     BinaryWithoutRhs,
     EmptyExpr,
+    Internal,
 }
 
 impl AsmMsg for AstMsg {
@@ -140,6 +170,7 @@ impl AsmMsg for AstMsg {
             BinaryWithoutLhs => "Binary operator expected an operand on its left",
             BinaryWithoutRhs => "Binary operator expected an operand on its right",
             EmptyExpr => "Constant expression is empty",
+            Internal => "Internal error of the assembler, this is a bug",
         }
     }
 }
@@ -192,6 +223,7 @@ impl AsmMsg for AstValidationMsg {
 #[derive(Debug, Copy, Clone)]
 pub enum OpMsg {
     NotFound,
+    Internal,
 }
 
 impl AsmMsg for OpMsg {
@@ -201,6 +233,7 @@ impl AsmMsg for OpMsg {
 
         match self {
             NotFound => "Could not find the corresponding opcode",
+            Internal => "Internal error of the assembler, this is a bug",
         }
     }
 
@@ -210,8 +243,23 @@ impl AsmMsg for OpMsg {
 #[derive(Debug, Copy, Clone)]
 pub enum ConstantsMsg {
     DuplicateKey,
-    MisplacedMarker,
+    /// Start of the block running past the marker, and by how many bytes.
+    Overlap{ block: usize, bytes: usize },
     FileReadFailed,
+    NoFilePath,
+    CompressedTooLarge,
+    NoStruct,
+    BadField,
+    NoSection,
+    BadVar,
+    BadEnum,
+    BadData,
+    BadFill,
+    BadLoad,
+    BadAlign,
+    /// Alignment in bytes, and by how many bytes the location is past a multiple of it.
+    Misaligned{ alignment: usize, bytes: usize },
+    Internal,
 }
 
 impl AsmMsg for ConstantsMsg {
@@ -220,8 +268,35 @@ impl AsmMsg for ConstantsMsg {
 
         match self {
             DuplicateKey => "Constant's key already existed",
-            MisplacedMarker => "The location of the marker does match its value",
+            Overlap{..} => "The code before this marker runs past its address",
             FileReadFailed => "The file to include could not be read",
+            NoFilePath => "The directive expected a file path",
+            CompressedTooLarge => "The compressed data does not fit in 64KB",
+            NoStruct => "No struct of this name is declared before",
+            BadField => "A field is a name followed by its size in bytes or the name of a struct",
+            NoSection => "Variables are reserved after a #ram directive giving the address of the section",
+            BadVar => "A variable is a name followed by its size in bytes or a struct, and an optional count",
+            BadEnum => "An enum is an optional prefix, start and step, followed by the names of its values",
+            BadData => "Data is a list of numbers, names, strings or expressions in parentheses",
+            BadFill => "A fill is a number of copies followed by their value",
+            BadLoad => "A #load block has a name and an address, ends with #endload, and can not contain a marker or another block",
+            BadAlign => "An alignment is a power of 2 up to 16, with an optional byte to fill with",
+            Misaligned{..} => "The location is not aligned",
+            Internal => "Internal error of the assembler, this is a bug",
+        }
+    }
+
+    fn detail(&self) -> Option<String> {
+        match self {
+            Self::Overlap{ block, bytes } => {
+                let unit = if *bytes == 1 { "byte" }else{ "bytes" };
+                Some(format!("the block from &{block:04X} is {bytes} {unit} too long"))
+            }
+            Self::Misaligned{ alignment, bytes } => {
+                let unit = if *bytes == 1 { "byte" }else{ "bytes" };
+                Some(format!("it is {bytes} {unit} past a multiple of &{alignment:X}"))
+            }
+            _ => None,
         }
     }
 }
@@ -233,6 +308,10 @@ pub enum ExprMsg {
     ConstantNotFound,
     CircularDependency,
     NegativeResult,
+    EnumOverflow,
+    DataOverflow,
+    RamOverflow,
+    Internal,
 }
 
 impl AsmMsg for ExprMsg {
@@ -244,6 +323,10 @@ impl AsmMsg for ExprMsg {
             ConstantNotFound => "The constant does not exist",
             CircularDependency => "Infinite loop of dependencies",
             NegativeResult => "The result of an expression cannot be negative",
+            EnumOverflow => "The value of the enum does not fit in a byte",
+            DataOverflow => "The value does not fit in the width of the data",
+            RamOverflow => "The variable does not fit below &FFFF",
+            Internal => "Internal error of the assembler, this is a bug",
         }
     }
 }
@@ -252,6 +335,8 @@ impl AsmMsg for ExprMsg {
 #[derive(Debug, Copy, Clone)]
 pub enum ConstantsValidationMsg {
     IdentNotFound,
+    /// Size of the variable or field, and of the access.
+    FieldWidth{ size: usize, width: usize },
 }
 
 impl AsmMsg for ConstantsValidationMsg {
@@ -260,6 +345,134 @@ impl AsmMsg for ConstantsValidationMsg {
 
         match self {
             IdentNotFound => "Use of undeclared identifier",
+            FieldWidth{..} => "The variable is read or written with a width that does not match its size",
+        }
+    }
+
+    fn detail(&self) -> Option<String> {
+        match self {
+            Self::FieldWidth{ size, width } => Some(format!("its size is {size}, the access takes {width}")),
+            _ => None,
+        }
+    }
+}
+
+/// Error variants when checking the cycles of instructions.
+#[derive(Debug, Copy, Clone)]
+pub enum CyclesMsg {
+    NoBudget,
+    OverBudget,
+}
+
+impl AsmMsg for CyclesMsg {
+    fn msg(&self) -> &'static str {
+        use CyclesMsg::*;
+
+        match self {
+            NoBudget => "The directive expected a number of cycles",
+            OverBudget => "The instructions take more cycles than the budget",
+        }
+    }
+}
+
+/// Error variants of `#assert`, `#print`, `#warn` and `#error` directives, and of the values of data.
+/// The variants holding the message of the directive display it.
+#[derive(Copy, Clone)]
+pub enum CheckMsg<'a> {
+    NoMessage,
+    Expr(ExprMsg),
+    Assert(&'a str),
+    Warning(&'a str),
+    Error(&'a str),
+}
+
+impl std::fmt::Debug for CheckMsg<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckMsg::NoMessage => write!(f, "NoMessage"),
+            CheckMsg::Expr(e) => write!(f, "{e:?}"),
+            CheckMsg::Assert(_) => write!(f, "AssertFailed"),
+            CheckMsg::Warning(_) => write!(f, "UserWarning"),
+            CheckMsg::Error(_) => write!(f, "UserError"),
+        }
+    }
+}
+
+impl AsmMsg for CheckMsg<'_> {
+    fn msg(&self) -> &str {
+        use CheckMsg::*;
+
+        match self {
+            NoMessage => "The directive expected a string literal before or after its expression",
+            Expr(e) => e.msg(),
+            Assert(msg)|Warning(msg)|Error(msg) => msg,
+        }
+    }
+}
+
+/// Warnings for code that builds but is likely a mistake.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LintMsg {
+    UnusedLabel,
+    UnusedConstant,
+    UnusedMacro,
+    Unreachable,
+    FallThrough,
+    SelfLoad,
+}
+
+impl LintMsg {
+
+    pub const ALL: [Self; 6] = [
+        Self::UnusedLabel,
+        Self::UnusedConstant,
+        Self::UnusedMacro,
+        Self::Unreachable,
+        Self::FallThrough,
+        Self::SelfLoad,
+    ];
+
+    /// Name used to configure the lint from the command line.
+    pub const fn name(self) -> &'static str {
+        use LintMsg::*;
+
+        match self {
+            UnusedLabel => "unused-label",
+            UnusedConstant => "unused-constant",
+            UnusedMacro => "unused-macro",
+            Unreachable => "unreachable",
+            FallThrough => "fall-through",
+            SelfLoad => "self-load",
+        }
+    }
+
+}
+
+impl AsmMsg for LintMsg {
+    fn msg(&self) -> &'static str {
+        use LintMsg::*;
+
+        match self {
+            UnusedLabel => "Label is never used",
+            UnusedConstant => "Constant is never used",
+            UnusedMacro => "Macro is never called",
+            Unreachable => "Code after an unconditional jump or return is never executed",
+            FallThrough => "Code falls through this label into data",
+            SelfLoad => "Register is loaded into itself",
+        }
+    }
+}
+
+/// Warnings when assembling over a base ROM.
+#[derive(Debug, Copy, Clone)]
+pub enum PatchMsg {
+    Unchanged,
+}
+
+impl AsmMsg for PatchMsg {
+    fn msg(&self) -> &'static str {
+        match self {
+            PatchMsg::Unchanged => "The bytes written from this marker are the same in the base ROM",
         }
     }
 }
@@ -270,29 +483,81 @@ pub enum EncodeErr {
     BadHeader,
     WriteFailed,
     CreateFailed,
+    DepsFailed,
+    ListingFailed,
+    BadRange,
+    TooLargeForIps,
+    Internal(SourceCtx),
 }
 
 impl EncodeErr {
 
-    const fn msg(self) -> &'static str {
+    pub const fn msg(self) -> &'static str {
         use EncodeErr::*;
 
         match self {
             BadHeader => "Bad ROM header",
             WriteFailed => "Could not write output file",
             CreateFailed => "Could not create output file",
+            DepsFailed => "Could not write dependency file",
+            ListingFailed => "Could not write listing file",
+            BadRange => "The range of addresses is not in the output",
+            TooLargeForIps => "IPS patches can only change the first 16MB",
+            Internal(_) => "Internal error of the assembler, this is a bug",
         }
     }
 
+    /// Name of the variant, without its values.
+    pub fn code(self) -> String {
+        let name = format!("{self:?}");
+        name.split([' ', '(', '{']).next().unwrap_or_default().into()
+    }
+
 }
 
 impl std::fmt::Display for EncodeErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let text = fmt::strip()
-            .info(&format!("({self:?}) "))
+        let mut strip = fmt::strip();
+
+        if let Self::Internal(source_ctx) = self {
+            strip = strip.debug(&format!("{source_ctx}\n"));
+        }
+
+        let text = strip
+            .info(&format!("({}) ", self.code()))
             .base(self.msg())
             .read();
 
         write!(f, "{text}")
     }
 }
+
+/// Panic of the assembler, caught so that it does not stop the process.
+#[derive(Debug, Clone)]
+pub struct InternalErr {
+    pub msg: String,
+}
+
+impl InternalErr {
+
+    /// Reads the message of a panic.
+    pub fn from_panic(payload: &(dyn std::any::Any + Send)) -> Self {
+        let msg = payload.downcast_ref::<&str>().map(|msg| msg.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+
+        Self{ msg }
+    }
+
+}
+
+impl std::fmt::Display for InternalErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = fmt::strip()
+            .info("(Internal) ")
+            .base(&format!("Internal error of the assembler, this is a bug: {}", self.msg))
+            .read();
+
+        write!(f, "{text}")
+    }
+}
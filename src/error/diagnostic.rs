@@ -0,0 +1,178 @@
+use crate::{
+    parse::source::Source,
+    lsp::json::Json,
+    error::{
+        ErrCtx,
+        asm::{ AsmErr, AsmMsg, EncodeErr, InternalErr },
+        init::{ ClargsErr, ReadErr, SplitErr },
+    },
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Owned version of an error, with its position in the source.
+/// It outlives the build, for tools that report errors in other ways than the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Name of the error variant.
+    pub code: String,
+    pub message: String,
+    /// Empty when the error is not related to a file.
+    pub file: String,
+    /// Line number, starting at 1, or 0 when the error is not related to a line.
+    pub line: usize,
+    /// Range of characters in the line, starting at 0.
+    pub columns: (usize, usize),
+    /// Word of the source or argument the error is about, can be empty.
+    pub word: String,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+
+    /// Error that is not related to a position in the source.
+    pub fn global(code: &str, message: &str) -> Self {
+        Self{
+            code: code.into(),
+            message: message.into(),
+            file: String::new(),
+            line: 0,
+            columns: (0, 0),
+            word: String::new(),
+            severity: Severity::Error,
+        }
+    }
+
+    /// Object with the stage of the build that reported the diagnostic.
+    pub fn to_json(&self, stage: &str) -> Json {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        Json::object([
+            ("stage", stage.into()),
+            ("severity", severity.into()),
+            ("code", self.code.as_str().into()),
+            ("message", self.message.as_str().into()),
+            ("file", self.file.as_str().into()),
+            ("line", self.line.into()),
+            ("columns", vec![self.columns.0.into(), self.columns.1.into()].into()),
+            ("word", self.word.as_str().into()),
+        ])
+    }
+
+}
+
+/// Errors of the build that can be written as a diagnostic.
+/// The source gives the position of the error in its file, when it is known.
+pub trait Diagnose: std::fmt::Display {
+    fn diagnostic(&self, source: Option<&Source>) -> Diagnostic;
+}
+
+impl<T: AsmMsg> Diagnose for AsmErr<'_, T> {
+    fn diagnostic(&self, source: Option<&Source>) -> Diagnostic {
+        let (line, columns) = self.err_ctx.position(source);
+
+        Diagnostic{
+            code: self.code(),
+            message: self.message(),
+            file: self.err_ctx.file().into(),
+            line,
+            columns,
+            word: self.err_ctx.word().into(),
+            severity: Severity::Error,
+        }
+    }
+}
+
+impl Diagnose for SplitErr<'_> {
+    fn diagnostic(&self, source: Option<&Source>) -> Diagnostic {
+        let line = source
+            .and_then(|source| source.get(self.file()))
+            .and_then(|input| input.lines().nth(self.line_number() - 1));
+
+        Diagnostic{
+            code: format!("{:?}", self.ty()),
+            message: self.description().into(),
+            file: self.file().into(),
+            line: self.line_number(),
+            columns: (0, line.map_or(0, |line| line.chars().count())),
+            word: self.line().trim().into(),
+            severity: Severity::Error,
+        }
+    }
+}
+
+impl Diagnose for ClargsErr<'_> {
+    fn diagnostic(&self, _: Option<&Source>) -> Diagnostic {
+        Diagnostic{ word: self.arg().into(), ..Diagnostic::global(&format!("{:?}", self.ty()), self.description()) }
+    }
+}
+
+impl Diagnose for EncodeErr {
+    fn diagnostic(&self, _: Option<&Source>) -> Diagnostic {
+        Diagnostic::global(&self.code(), self.msg())
+    }
+}
+
+impl Diagnose for InternalErr {
+    fn diagnostic(&self, _: Option<&Source>) -> Diagnostic {
+        Diagnostic::global("Internal", &format!("Internal error of the assembler, this is a bug: {}", self.msg))
+    }
+}
+
+impl Diagnose for ReadErr<'_> {
+    fn diagnostic(&self, _: Option<&Source>) -> Diagnostic {
+        let diagnostic = Diagnostic::global(&format!("{:?}", self.error().kind()), &self.error().to_string());
+        Diagnostic{ file: self.path().into(), word: self.path().into(), ..diagnostic }
+    }
+}
+
+impl<'a> ErrCtx<'a> {
+
+    /// Line number and range of characters of the word in its file.
+    /// Uses the whole line if the word cannot be found in the source.
+    pub fn position(&self, source: Option<&Source>) -> (usize, (usize, usize)) {
+        let input = source.and_then(|source| source.get(self.file));
+
+        // The word is a slice of the file's content, unless it comes from a macro argument.
+        let offset = input.and_then(|input| {
+            let start = input.content.as_ptr() as usize;
+            let word = self.word.as_ptr() as usize;
+            (word >= start && word + self.word.len() <= start + input.content.len()).then(|| word - start)
+        });
+
+        if let (Some(input), Some(offset), false) = (input, offset, self.word.is_empty()) {
+            let before = &input.content[..offset];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = before.matches('\n').count() + 1;
+            let column = before[line_start..].chars().count();
+
+            return (line, (column, column + self.word.chars().count()));
+        }
+
+        let len = input
+            .and_then(|input| input.lines().nth(self.line_number.saturating_sub(1)))
+            .map_or(0, |line| line.chars().count());
+
+        (self.line_number, (0, len))
+    }
+
+    pub const fn file(&self) -> &'a str {
+        self.file
+    }
+
+    pub const fn word(&self) -> &'a str {
+        self.word
+    }
+
+    pub const fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+}
@@ -8,6 +8,14 @@ pub enum ClargsErrType {
     NoOutput,
     TooManyParams,
     UnknownArg,
+    UnknownLint,
+    NoEndLabel,
+    LabelNotFound,
+    UnknownFormat,
+    BadAddress,
+    NoEndAddress,
+    RangeOfRom,
+    NoBase,
 }
 
 /// Command line arguments error.
@@ -23,6 +31,10 @@ impl<'a> ClargsErr<'a> {
         Self { ty, msg }
     }
 
+    pub const fn ty(&self) -> ClargsErrType { self.ty }
+    /// Argument the error is about, can be empty.
+    pub const fn arg(&self) -> &'a str { self.msg }
+
     pub const fn description(&self) -> &'static str {
         use ClargsErrType::*;
 
@@ -31,6 +43,14 @@ impl<'a> ClargsErr<'a> {
             NoOutput => "No output file specified",
             TooManyParams => "Too many parameters in argument",
             UnknownArg => "Unknown argument",
+            UnknownLint => "Unknown lint",
+            NoEndLabel => "Cycles are counted between two labels",
+            LabelNotFound => "Label not found, or not after the first one",
+            UnknownFormat => "Unknown output format, expected gb, hex, bin, ips or bps",
+            BadAddress => "Bad address, expected a decimal number, or hexadecimal after '&' or '0x'",
+            NoEndAddress => "A range is given by its start and end addresses",
+            RangeOfRom => "A range of addresses can only be written in the hex or bin formats",
+            NoBase => "Patches are made against a base ROM, given with -b",
         }
     }
 
@@ -59,16 +79,22 @@ pub enum SplitErrType {
 #[derive(Debug)]
 pub struct SplitErr<'a> {
     ty: SplitErrType,
+    file: &'a str,
     line: &'a str,
     line_number: usize,
 }
 
 impl<'a> SplitErr<'a> {
 
-    pub const fn new(ty: SplitErrType, line: &'a str, line_number: usize) -> Self {
-        Self { ty, line, line_number }
+    pub const fn new(ty: SplitErrType, file: &'a str, line: &'a str, line_number: usize) -> Self {
+        Self { ty, file, line, line_number }
     }
 
+    pub const fn ty(&self) -> SplitErrType { self.ty }
+    pub const fn file(&self) -> &'a str { self.file }
+    pub const fn line_number(&self) -> usize { self.line_number }
+    pub const fn line(&self) -> &'a str { self.line }
+
     pub const fn description(&self) -> &'static str {
         use SplitErrType::*;
 
@@ -84,9 +110,38 @@ impl<'a> SplitErr<'a> {
 impl<'a> std::fmt::Display for SplitErr<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ty = &format!("({:?}) ", self.ty);
-        let msg = &format!("{}\nl{}:    {}", self.description(), self.line_number, self.line);
+        let msg = &format!("{}\n{}:{}:    {}", self.description(), self.file, self.line_number, self.line);
         let text = fmt::strip().info(ty).bold(msg).read();
 
         write!(f, "{text}")
     }
 }
+
+/// A file of the build could not be read, such as the main file or the base ROM.
+#[derive(Debug)]
+pub struct ReadErr<'a> {
+    path: &'a str,
+    error: std::io::Error,
+}
+
+impl<'a> ReadErr<'a> {
+
+    pub const fn new(path: &'a str, error: std::io::Error) -> Self {
+        Self { path, error }
+    }
+
+    pub const fn path(&self) -> &'a str { self.path }
+    pub const fn error(&self) -> &std::io::Error { &self.error }
+
+}
+
+impl<'a> std::fmt::Display for ReadErr<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = fmt::strip()
+            .info(&format!("({:?}) ", self.error.kind()))
+            .base(&format!("{}{}", self.error, fmt::ln_if(self.path)))
+            .read();
+
+        write!(f, "{text}")
+    }
+}
@@ -10,6 +10,9 @@ pub mod init;
 #[macro_use]
 pub mod asm;
 
+/// Errors with their position, independent from the build.
+pub mod diagnostic;
+
 use crate::{
     parse::{ prepare::ParsedToken, lex::TokenType },
     token::{ Token, read::TokenRef },
@@ -18,13 +21,17 @@ use crate::{
 /// Used mostly in recursion fail-safes.
 pub const ITERATION_LIMIT: usize = 1000;
 
-/// Panics with the source context.
-/// e.g.    bug!("Oopsie! Assembler no worky...")
+/// Creates an error for a state that no source should lead to, a bug of the assembler.
+/// It is returned like the other errors of its stage, with the location in the code of the assembler.
+/// Arguments:
+/// - Error enum type, with an `Internal` variant
+/// - `ErrCtx` object
+///
+/// e.g.    bug!(AstMsg, token.into())
 macro_rules! bug {
-    ($s:literal) => {{
-        println!("Internal error at '{}':\n{}\nThis is a bug.", source!(), $s);
-        std::process::exit(1);
-    }}
+    ($ty:ty, $ctx:expr) => {
+        err!($ty, <$ty>::Internal, $ctx)
+    }
 }
 
 /// Creates a `SourceCtx` containing its location in the source code.
@@ -34,7 +41,7 @@ macro_rules! source {
 }
 
 /// Stores a location in the source code.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub struct SourceCtx {
     pub file: &'static str,
     pub line: u32,
@@ -70,7 +77,8 @@ impl<'a> ErrCtx<'a> {
         Self{ ty, file, line_number, line, word }
     }
 
-    /// Finds the start of `self.word` relative to `self.line`.
+    /// Finds the start of `self.word` relative to `self.line`, in bytes.
+    /// The word must be a slice of the line.
     pub fn word_start(self) -> Option<usize> {
         if self.line.is_empty() || self.word.is_empty() {
             return None;
@@ -79,22 +87,7 @@ impl<'a> ErrCtx<'a> {
         let line = self.line.as_ptr() as usize;
         let word = self.word.as_ptr() as usize;
 
-        if line > word {
-            return None;
-        }
-
-        let start_byte = word - line;
-        let mut bytes = 0;
-
-        for (i, ch) in self.line.chars().enumerate() {
-            bytes += ch.len_utf8();
-
-            if bytes >= start_byte {
-                return Some(i);
-            }
-        }
-
-        None
+        (word >= line && word + self.word.len() <= line + self.line.len()).then(|| word - line)
     }
 
 }
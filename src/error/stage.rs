@@ -1,47 +1,198 @@
+use crate::{
+    program::fmt,
+    parse::source::Source,
+    error::diagnostic::{ Diagnostic, Diagnose, Severity },
+};
 
-#![allow(clippy::needless_pass_by_value)]
+use std::{ cell::RefCell, collections::HashSet };
 
-use crate::program::fmt;
+/// Collects the errors of the stages of the build, to write them all at the end,
+/// as text or as JSON diagnostics with one object per line.
+pub struct Report<'a> {
+    json: bool,
+    /// Nothing is written, the diagnostics are only returned by `finish`.
+    silent: bool,
+    /// Gives the position of the errors, once the source is read.
+    source: Option<&'a Source>,
+    errors: RefCell<Vec<Entry>>,
+    warnings: RefCell<Vec<Diagnostic>>,
+    /// Names defined on lines with errors.
+    poisoned: RefCell<HashSet<String>>,
+}
 
-fn stage_err<E: std::fmt::Display + Sized>(e: E, msg: &str) {
-    let msg = fmt::strip().err("Compilation Failed.\n\n    ").info(msg).read();
-    eprintln!("{e}\n{msg}\n");
+/// Error of a stage, kept after the tokens it refers to are dropped.
+struct Entry {
+    stage: &'static str,
+    msg: &'static str,
+    diagnostic: Diagnostic,
+    text: String,
 }
 
-fn stage_err_vec<E: std::fmt::Display + Sized>(ev: Vec<E>, msg: &str) {
-    let f = |(i, e)| if i != ev.len() - 1 {
-        format!("{e}\n")
-    }else {
-        format!("{e}")
-    };
-
-    let msg = fmt::strip()
-        .err("Build failed with")
-        .bold(&format!(" {} ", ev.len()))
-        .err("errors:\n\n    ")
-        .info(msg)
-        .read();
-
-    eprintln!("{}\n{}\n", ev.iter().enumerate().map(f).collect::<String>(), msg);
+/// Lines of the errors reported by a failing stage.
+/// The build can be tried again without them.
+#[derive(Debug, Default)]
+pub struct Failure {
+    pub lines: Vec<(String, usize)>,
 }
 
 macro_rules! stage_err { ($fn:ident, $lit:literal) => {
-    pub fn $fn<E: std::fmt::Display + Sized>(e: E) { stage_err(e, $lit); }   
+    pub fn $fn<E: Diagnose>(&self, e: E) -> Failure { self.stage_err_vec(stringify!($fn), vec![e], $lit) }
 }}
 
 macro_rules! stage_err_vec { ($fn:ident, $lit:literal) => {
-    pub fn $fn<E: std::fmt::Display + Sized>(ev: Vec<E>) { stage_err_vec(ev, $lit); }
+    pub fn $fn<E: Diagnose>(&self, ev: Vec<E>) -> Failure { self.stage_err_vec(stringify!($fn), ev, $lit) }
 }}
 
-stage_err!(clargs, "Invalid command line arguments.");
-stage_err!(source, "Could not read source file.");
-stage_err_vec!(split, "Could not split words from source file.");
-stage_err_vec!(parse, "Could not parse words.");
-stage_err_vec!(ast, "Could not build the token tree.");
-stage_err_vec!(macros, "Could not expand macros.");
-stage_err_vec!(ops, "Could not find instructions.");
-stage_err_vec!(ast_validation, "Could not validate the token tree.");
-stage_err!(constants, "Could not collect constants.");
-stage_err_vec!(expressions, "Could not evaluate expressions in constants.");
-stage_err_vec!(constants_validation, "Could not validate the constants.");
-stage_err!(encode, "Could not encode output.");
+impl<'a> Report<'a> {
+
+    pub fn new(json: bool) -> Self {
+        Self{
+            json,
+            silent: false,
+            source: None,
+            errors: RefCell::new(vec![]),
+            warnings: RefCell::new(vec![]),
+            poisoned: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Report of a build in memory, that does not write to the terminal.
+    pub fn silent() -> Self {
+        Self{ silent: true, ..Self::new(false) }
+    }
+
+    pub fn with_source<'b>(self, source: &'b Source) -> Report<'b> {
+        Report{
+            json: self.json,
+            silent: self.silent,
+            source: Some(source),
+            errors: self.errors,
+            warnings: self.warnings,
+            poisoned: self.poisoned,
+        }
+    }
+
+    /// The uses of a name whose definition has an error are not reported,
+    /// their lines are still left out of the next try.
+    pub fn poison(&self, name: &str) {
+        self.poisoned.borrow_mut().insert(name.into());
+    }
+
+    pub const fn is_silent(&self) -> bool {
+        self.silent
+    }
+
+    fn json<E: Diagnose + ?Sized>(&self, stage: &str, e: &E, severity: Severity) {
+        let diagnostic = Diagnostic{ severity, ..e.diagnostic(self.source) };
+        eprintln!("{}", diagnostic.to_json(stage));
+    }
+
+    fn stage_err_vec<E: Diagnose>(&self, stage: &'static str, ev: Vec<E>, msg: &'static str) -> Failure {
+        let mut errors = self.errors.borrow_mut();
+        let mut failure = Failure::default();
+
+        for e in ev {
+            let diagnostic = e.diagnostic(self.source);
+
+            if diagnostic.line != 0 {
+                failure.lines.push((diagnostic.file.clone(), diagnostic.line));
+            }
+
+            let missing = matches!(diagnostic.code.as_str(), "IdentNotFound"|"ConstantNotFound");
+
+            if missing && self.poisoned.borrow().contains(&diagnostic.word) {
+                continue;
+            }
+
+            // A constant is evaluated again by each constant that uses it.
+            if errors.iter().any(|e| e.diagnostic == diagnostic) {
+                continue;
+            }
+
+            errors.push(Entry{ stage, msg, diagnostic, text: e.to_string() });
+        }
+
+        failure
+    }
+
+    pub fn failed(&self) -> bool {
+        !self.errors.borrow().is_empty()
+    }
+
+    /// Writes the errors, sorted by file and line.
+    /// Returns them with the warnings, as diagnostics.
+    pub fn finish(self) -> Vec<Diagnostic> {
+        let mut errors = self.errors.into_inner();
+
+        // Messages of the failing stages, in the order they ran.
+        let mut msgs = vec![];
+
+        for e in &errors {
+            if !msgs.contains(&e.msg) {
+                msgs.push(e.msg);
+            }
+        }
+
+        errors.sort_by(|a, b| (&a.diagnostic.file, a.diagnostic.line).cmp(&(&b.diagnostic.file, b.diagnostic.line)));
+
+        if self.json && !self.silent {
+            errors.iter().for_each(|e| eprintln!("{}", e.diagnostic.to_json(e.stage)));
+        }
+
+        if !self.json && !self.silent && !errors.is_empty() {
+            let msg = fmt::strip()
+                .err("Build failed with")
+                .bold(&format!(" {} ", errors.len()))
+                .err(if errors.len() == 1 { "error:\n\n    " } else { "errors:\n\n    " })
+                .info(&msgs.join("\n    "))
+                .read();
+
+            eprintln!("{}\n{}\n", errors.iter().map(|e| e.text.as_str()).collect::<Vec<_>>().join("\n"), msg);
+        }
+
+        errors.into_iter().map(|e| e.diagnostic).chain(self.warnings.into_inner()).collect()
+    }
+
+    /// Warnings do not stop the build, they are written right away.
+    pub fn warnings(&self, ev: &[&dyn Diagnose]) {
+        self.warnings.borrow_mut().extend(ev.iter().map(|e| Diagnostic{
+            severity: Severity::Warning,
+            ..e.diagnostic(self.source)
+        }));
+
+        if ev.is_empty() || self.silent {
+            return;
+        }
+
+        if self.json {
+            ev.iter().for_each(|e| self.json("warnings", *e, Severity::Warning));
+            return;
+        }
+
+        let msg = fmt::strip()
+            .info("Build has")
+            .bold(&format!(" {} ", ev.len()))
+            .info("warnings.")
+            .read();
+
+        eprintln!("{}\n{}\n", ev.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"), msg);
+    }
+
+    stage_err!(clargs, "Invalid command line arguments.");
+    stage_err!(source, "Could not read source file.");
+    stage_err_vec!(split, "Could not split words from source file.");
+    stage_err_vec!(parse, "Could not parse words.");
+    stage_err_vec!(ast, "Could not build the token tree.");
+    stage_err_vec!(macros, "Could not expand macros.");
+    stage_err_vec!(ops, "Could not find instructions.");
+    stage_err_vec!(cycles, "Some instructions do not fit their budget of cycles.");
+    stage_err_vec!(ast_validation, "Could not validate the token tree.");
+    stage_err!(constants, "Could not collect constants.");
+    stage_err_vec!(expressions, "Could not evaluate expressions in constants.");
+    stage_err_vec!(constants_validation, "Could not validate the constants.");
+    stage_err_vec!(checks, "Some assertions failed or errors were reported.");
+    stage_err_vec!(lints, "Some lints are reported as errors.");
+    stage_err!(encode, "Could not encode output.");
+    stage_err!(internal, "The assembler stopped on an internal error.");
+
+}
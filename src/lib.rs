@@ -0,0 +1,34 @@
+
+//! Assembler for the Game Boy.
+//! `assemble` builds a program in memory, `run` is the command line program.
+
+#![deny(unreachable_patterns, non_snake_case)]
+
+/// Error types for the different compilation stages
+#[macro_use]
+mod error;
+
+/// Controls the execution of the assembler.
+/// It also provides tools for cli arguments and outputs.
+#[macro_use]
+mod program;
+
+/// Tools for reading the source file and transforming it into tokens.
+mod parse;
+
+/// Constructs the hierarchy of tokens.
+mod token;
+
+/// Prepares the final result for the binary output.
+mod write;
+
+/// Language server for editors.
+mod lsp;
+
+#[cfg(target_family="unix")]
+#[cfg(test)]
+mod tests;
+
+pub use program::{ run, embed::{ assemble, Assembly } };
+pub use error::diagnostic::{ Diagnostic, Severity };
+pub use parse::provider::{ Provider, Disk, Memory };
@@ -0,0 +1,126 @@
+use crate::{
+    lsp::utf16,
+    program::{ self, clargs::Clargs, embed::Assembly },
+    parse::{ source::Source, split::Split, prepare::{ self, ParsedToken }, files::Files, lex::TokenType::* },
+    token::Value,
+    write::encode::Format,
+    error::{ ErrCtx, stage::Report, diagnostic::Diagnostic },
+};
+
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolKind {
+    Label,
+    Constant,
+    Macro,
+}
+
+/// Definition of a label, constant or macro.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub file: String,
+    pub line: usize,
+    pub columns: (usize, usize),
+    /// Evaluated value, when the build succeeds.
+    pub value: Option<Value<'static>>,
+}
+
+/// Result of building a file without writing the output.
+#[derive(Default)]
+pub struct Analysis {
+    pub diagnostics: Vec<Diagnostic>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Analysis {
+
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name)
+    }
+
+}
+
+/// Builds a file like the command line, with the symbols and the lint levels of `settings`,
+/// and collects the errors, warnings and definitions.
+/// Columns are in UTF-16 code units, as in the protocol.
+pub fn analyze(path: &str, settings: &Clargs, files: Rc<Files>) -> Analysis {
+    let clargs = Clargs{
+        path,
+        symbols: settings.symbols.clone(),
+        search_paths: settings.search_paths.clone(),
+        lints: settings.lints.clone(),
+        // Files other than a ROM are allowed.
+        format: Format::Bin,
+        ..Default::default()
+    };
+
+    let (assembly, diagnostics) = program::compile(&clargs, Rc::clone(&files), Report::silent(), None);
+
+    let mut symbols = match Source::new(path, &clargs.search_paths, Rc::clone(&files)) {
+        Ok(source) => {
+            let (splits, _) = Split::all(&source, &clargs.symbols);
+            let (parsed_tokens, _) = prepare::parse_all(&source, &splits);
+            definitions(&parsed_tokens, &source, assembly.as_ref())
+        }
+
+        Err(_) => vec![],
+    };
+
+    let line = |file: &str, line: usize| files.read_to_string(file).ok()
+        .and_then(|text| text.lines().nth(line.saturating_sub(1)).map(String::from))
+        .unwrap_or_default();
+
+    let diagnostics = diagnostics.into_iter().map(|d| {
+        let text = line(&d.file, d.line);
+        Diagnostic{ columns: (utf16(&text, d.columns.0), utf16(&text, d.columns.1)), ..d }
+    }).collect();
+
+    for symbol in &mut symbols {
+        let text = line(&symbol.file, symbol.line);
+        symbol.columns = (utf16(&text, symbol.columns.0), utf16(&text, symbol.columns.1));
+    }
+
+    Analysis{ diagnostics, symbols }
+}
+
+/// Finds the definitions of labels, constants and macros, with their value in `assembly`.
+fn definitions(tokens: &[ParsedToken], source: &Source, assembly: Option<&Assembly>) -> Vec<Symbol> {
+    let mut symbols = vec![];
+
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| &tokens[i])
+            .filter(|previous| previous.file == token.file && previous.line_number == token.line_number);
+
+        let kind = match (previous.map(|p| p.ty), token.ty) {
+            (_, Label|NamedMark) => SymbolKind::Label,
+            (Some(DefB|DefW|Rle|Lz|Struct|Var), Identifier) => SymbolKind::Constant,
+            (Some(Macro), MacroIdent) => SymbolKind::Macro,
+            _ => continue,
+        };
+
+        let Ok(name) = token.value.as_str() else { continue };
+
+        // Point to the name rather than the whole word.
+        let ctx = ErrCtx::new(token.ty, token.file, token.line_number, token.line, name);
+        let (line, columns) = ctx.position(Some(source));
+
+        let value = assembly.and_then(|assembly| assembly.symbol(name)).map(|value| match previous.map(|p| p.ty) {
+            Some(DefB) => Value::U8(value as u8),
+            _ => Value::U16(value as u16),
+        });
+
+        symbols.push(Symbol{
+            name: name.into(),
+            kind,
+            file: token.file.into(),
+            line,
+            columns,
+            value,
+        });
+    }
+
+    symbols
+}
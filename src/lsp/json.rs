@@ -0,0 +1,299 @@
+// <https://www.rfc-editor.org/rfc/rfc8259>
+
+use std::fmt::Write;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Members keep their order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser{ chars: text.chars().peekable() };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+
+        match parser.chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("Unexpected '{c}' after the value.")),
+        }
+    }
+
+    /// Creates an object from its members.
+    pub fn object<const N: usize>(members: [(&str, Self); N]) -> Self {
+        Self::Object(members.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Member of an object, or `Null`.
+    pub fn get(&self, key: &str) -> &Self {
+        match self {
+            Self::Object(members) => members.iter()
+                .find(|(k, _)| k == key)
+                .map_or(&Self::Null, |(_, v)| v),
+
+            _ => &Self::Null,
+        }
+    }
+
+    /// Follows a path of members, e.g. `["textDocument", "uri"]`.
+    pub fn at(&self, path: &[&str]) -> &Self {
+        path.iter().fold(self, |value, key| value.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        if let Self::String(s) = self { Some(s) }else{ None }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Self::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> &[Self] {
+        if let Self::Array(a) = self { a }else{ &[] }
+    }
+
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+}
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(b) => write!(f, "{b}"),
+
+            Self::Number(n) => {
+                if n.is_finite() { write!(f, "{n}") }else{ write!(f, "null") }
+            }
+
+            Self::String(s) => write_str(f, s),
+
+            Self::Array(values) => {
+                f.write_char('[')?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 { f.write_char(',')?; }
+                    write!(f, "{value}")?;
+                }
+
+                f.write_char(']')
+            }
+
+            Self::Object(members) => {
+                f.write_char('{')?;
+
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i != 0 { f.write_char(',')?; }
+                    write_str(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self { Self::String(s.into()) }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self { Self::String(s) }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self { Self::Bool(b) }
+}
+
+impl From<usize> for Json {
+    fn from(n: usize) -> Self { Self::Number(n as f64) }
+}
+
+impl From<Vec<Self>> for Json {
+    fn from(values: Vec<Self>) -> Self { Self::Array(values) }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| matches!(c, ' '|'\t'|'\n'|'\r')).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("Expected '{expected}', found '{c}'.")),
+            None => Err(format!("Expected '{expected}', found the end of the text.")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(format!("Unexpected '{c}'.")),
+            None => Err("Unexpected end of the text.".into()),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        for c in word.chars() {
+            self.expect(c)?;
+        }
+
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let mut text = String::new();
+
+        while let Some(c) = self.chars.next_if(|c| matches!(c, '0'..='9'|'-'|'+'|'.'|'e'|'E')) {
+            text.push(c);
+        }
+
+        text.parse().map(Json::Number).map_err(|_| format!("Invalid number '{text}'."))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.chars.next().ok_or("Unterminated string.")? {
+                '"' => return Ok(s),
+
+                '\\' => match self.chars.next().ok_or("Unterminated string.")? {
+                    '"' => s.push('"'),
+                    '\\' => s.push('\\'),
+                    '/' => s.push('/'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+
+                    'u' => {
+                        let high = self.hex4()?;
+
+                        // Characters outside the basic plane are written as surrogate pairs.
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            self.expect('\\')?;
+                            self.expect('u')?;
+                            let low = self.hex4()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        }else {
+                            high
+                        };
+
+                        s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+
+                    c => return Err(format!("Invalid escape '\\{c}'.")),
+                },
+
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let c = self.chars.next().ok_or("Unterminated string.")?;
+            code = code * 16 + c.to_digit(16).ok_or_else(|| format!("Invalid hex digit '{c}'."))?;
+        }
+
+        Ok(code)
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(Json::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err("Expected ',' or ']' in array.".into()),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(Json::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(members)),
+                _ => return Err("Expected ',' or '}' in object.".into()),
+            }
+        }
+    }
+
+}
@@ -0,0 +1,386 @@
+/// Reads and writes JSON.
+pub mod json;
+
+/// Messages of the protocol over stdio.
+pub mod rpc;
+
+/// Builds files to find errors and definitions.
+pub mod analysis;
+
+use crate::{
+    lsp::{ json::Json, analysis::{ Analysis, Symbol, SymbolKind } },
+    program::clargs::Clargs,
+    parse::{ files::Files, library, lex::TokenType::{ self, InstrName }, text },
+    token::{ Value, lint::Level },
+    error::diagnostic::{ Diagnostic, Severity },
+};
+
+use std::{ collections::HashMap, io, rc::Rc };
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+
+const SEVERITY_ERROR: usize = 1;
+const SEVERITY_WARNING: usize = 2;
+
+/// Kinds of completion items.
+const COMPLETION_FUNCTION: usize = 3;
+const COMPLETION_KEYWORD: usize = 14;
+const COMPLETION_REFERENCE: usize = 18;
+const COMPLETION_CONSTANT: usize = 21;
+
+/// Open document.
+struct Document {
+    path: String,
+    analysis: Analysis,
+}
+
+struct Server<'a> {
+    /// Contents of open documents are kept as overlays.
+    files: Rc<Files>,
+    /// Search paths, symbols and lint levels of the builds, without a path.
+    settings: Clargs<'a>,
+    /// Open documents, by URI.
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+/// Runs a language server over stdio until the client exits.
+///
+/// List of arguments, as for a build:
+/// -I \[DIRECTORIES\]        Search paths for included files, optional.
+/// -D \[SYMBOLS\]            Defined symbols, optional.
+/// -A \[LINTS\]              Allowed lints, optional.
+/// -W \[LINTS\]              Lints reported as warnings, optional.
+/// -E \[LINTS\]              Lints reported as errors, optional.
+pub fn run(args: &[String]) -> Result<(), ()> {
+    let mut settings = Clargs::default();
+    let mut flag = "";
+
+    for arg in args {
+        match arg.as_str() {
+            "-I"|"-D"|"-A"|"-W"|"-E" => flag = arg,
+            _ => match flag {
+                "-I" => settings.search_paths.push(arg),
+                "-D" => settings.symbols.push(arg),
+                "-A" => settings.lints.set(arg, Level::Allow)?,
+                "-W" => settings.lints.set(arg, Level::Warn)?,
+                "-E" => settings.lints.set(arg, Level::Deny)?,
+                _ => return Err(()),
+            }
+        }
+    }
+
+    let mut server = Server{
+        files: Rc::default(),
+        settings,
+        documents: HashMap::new(),
+        shutdown: false,
+    };
+
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+
+    while let Some(content) = rpc::read(&mut input).map_err(|_| ())? {
+        let messages = match Json::parse(&content) {
+            Ok(message) => server.handle(&message),
+            Err(e) => vec![rpc::error(Json::Null, PARSE_ERROR, &e)],
+        };
+
+        for message in &messages {
+            rpc::write(&mut output, message).map_err(|_| ())?;
+        }
+
+        if server.exit(&content) {
+            return if server.shutdown { Ok(()) }else{ Err(()) };
+        }
+    }
+
+    Ok(())
+}
+
+impl<'a> Server<'a> {
+
+    fn exit(&self, content: &str) -> bool {
+        Json::parse(content).is_ok_and(|message| message.get("method").as_str() == Some("exit"))
+    }
+
+    /// Returns the messages to send back.
+    fn handle(&mut self, message: &Json) -> Vec<Json> {
+        let id = message.get("id");
+        let params = message.get("params");
+
+        let Some(method) = message.get("method").as_str() else {
+            // Responses from the client are ignored.
+            return vec![];
+        };
+
+        if !id.is_null() {
+            let result = match method {
+                "initialize" => Ok(capabilities()),
+                "shutdown" => { self.shutdown = true; Ok(Json::Null) }
+                "textDocument/definition" => Ok(self.definition(params)),
+                "textDocument/hover" => Ok(self.hover(params)),
+                "textDocument/completion" => Ok(self.completion(params)),
+                _ if self.shutdown => Err((INVALID_REQUEST, "The server is shutting down")),
+                _ => Err((METHOD_NOT_FOUND, "Unknown method")),
+            };
+
+            return vec![match result {
+                Ok(result) => rpc::response(id.clone(), result),
+                Err((code, msg)) => rpc::error(id.clone(), code, msg),
+            }];
+        }
+
+        let uri = params.at(&["textDocument", "uri"]).as_str().unwrap_or_default().to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params.at(&["textDocument", "text"]).as_str().unwrap_or_default();
+                let path = uri_to_path(&uri);
+
+                self.files.set_overlay(&path, text.into());
+                self.documents.insert(uri, Document{ path, analysis: Analysis::default() });
+            }
+
+            "textDocument/didChange" => {
+                let Some(document) = self.documents.get(&uri) else { return vec![] };
+
+                // Full synchronisation, the last change is the whole text.
+                if let Some(text) = params.get("contentChanges").as_array().last() {
+                    self.files.set_overlay(&document.path, text.get("text").as_str().unwrap_or_default().into());
+                }
+            }
+
+            "textDocument/didClose" => {
+                if let Some(document) = self.documents.remove(&uri) {
+                    self.files.remove_overlay(&document.path);
+                }
+
+                return vec![publish(&uri, "", &[])];
+            }
+
+            _ => return vec![],
+        }
+
+        // Other documents may import the one that changed.
+        self.analyze()
+    }
+
+    /// Builds all the open documents and returns their diagnostics.
+    fn analyze(&mut self) -> Vec<Json> {
+        let mut messages = vec![];
+
+        for (uri, document) in &mut self.documents {
+            document.analysis = analysis::analyze(&document.path, &self.settings, Rc::clone(&self.files));
+            messages.push(publish(uri, &document.path, &document.analysis.diagnostics));
+        }
+
+        messages
+    }
+
+    /// Finds the symbol under the cursor.
+    fn symbol(&self, params: &Json) -> Option<&Symbol> {
+        let uri = params.at(&["textDocument", "uri"]).as_str()?;
+        let document = self.documents.get(uri)?;
+        let line = params.at(&["position", "line"]).as_usize()?;
+        let character = params.at(&["position", "character"]).as_usize()?;
+
+        let text = self.files.read_to_string(&document.path).ok()?;
+        let word = word_at(text.lines().nth(line)?, character)?;
+
+        document.analysis.symbol(&word)
+    }
+
+    fn definition(&self, params: &Json) -> Json {
+        self.symbol(params)
+            .filter(|symbol| !library::is_library(&symbol.file))
+            .map_or(Json::Null, |symbol| Json::object([
+                ("uri", path_to_uri(&symbol.file).into()),
+                ("range", range(symbol.line, symbol.columns)),
+            ]))
+    }
+
+    fn hover(&self, params: &Json) -> Json {
+        let Some(symbol) = self.symbol(params) else {
+            return Json::Null;
+        };
+
+        let (declaration, detail) = match symbol.kind {
+            SymbolKind::Label => (format!(":{}", symbol.name), "Address"),
+            SymbolKind::Constant if matches!(symbol.value, Some(Value::U8(_))) => {
+                (format!("#db {}", symbol.name), "Value")
+            }
+
+            SymbolKind::Constant => (format!("#dw {}", symbol.name), "Value"),
+            SymbolKind::Macro => (format!("#macro {}.", symbol.name), ""),
+        };
+
+        let mut value = format!("```gbz80\n{declaration}\n```");
+
+        if let Some(v) = symbol.value {
+            value.push_str(&format!("\n{detail}: `{}`", format_value(v)));
+        }
+
+        Json::object([("contents", Json::object([("kind", "markdown".into()), ("value", value.into())]))])
+    }
+
+    fn completion(&self, params: &Json) -> Json {
+        let instructions = TokenType::WORDS.iter()
+            .filter(|word| TokenType::get_by_word(word).is_some_and(|ty| ty.parent_type() == InstrName))
+            .map(|word| Json::object([("label", (*word).into()), ("kind", COMPLETION_KEYWORD.into())]));
+
+        let symbols = params.at(&["textDocument", "uri"]).as_str()
+            .and_then(|uri| self.documents.get(uri))
+            .map(|document| document.analysis.symbols.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|symbol| {
+                let kind = match symbol.kind {
+                    SymbolKind::Label => COMPLETION_REFERENCE,
+                    SymbolKind::Constant => COMPLETION_CONSTANT,
+                    SymbolKind::Macro => COMPLETION_FUNCTION,
+                };
+
+                let label = match symbol.kind {
+                    SymbolKind::Macro => format!("{}.", symbol.name),
+                    _ => symbol.name.clone(),
+                };
+
+                let detail = symbol.value.map(format_value).unwrap_or_default();
+                Json::object([("label", label.into()), ("kind", kind.into()), ("detail", detail.into())])
+            });
+
+        Json::Array(instructions.chain(symbols).collect())
+    }
+
+}
+
+fn capabilities() -> Json {
+    Json::object([
+        ("capabilities", Json::object([
+            // Full synchronisation of the documents.
+            ("textDocumentSync", 1.into()),
+            ("definitionProvider", true.into()),
+            ("hoverProvider", true.into()),
+            ("completionProvider", Json::object([])),
+        ])),
+        ("serverInfo", Json::object([("name", env!("CARGO_PKG_NAME").into())])),
+    ])
+}
+
+/// Errors from other files are shown at the start of the document, their warnings are not shown.
+fn publish(uri: &str, path: &str, diagnostics: &[Diagnostic]) -> Json {
+    let shown = diagnostics.iter().filter(|d| d.severity == Severity::Error || d.file == path);
+
+    let diagnostics = shown.map(|d| {
+        let (range, message) = if d.file == path {
+            (range(d.line, d.columns), d.message.clone())
+        }else if d.file.is_empty() {
+            (range(1, (0, 0)), d.message.clone())
+        }else {
+            (range(1, (0, 0)), format!("{}:{}: {}", d.file, d.line, d.message))
+        };
+
+        let severity = match d.severity {
+            Severity::Error => SEVERITY_ERROR,
+            Severity::Warning => SEVERITY_WARNING,
+        };
+
+        Json::object([
+            ("range", range),
+            ("severity", severity.into()),
+            ("code", d.code.as_str().into()),
+            ("source", env!("CARGO_PKG_NAME").into()),
+            ("message", message.into()),
+        ])
+    }).collect::<Vec<_>>();
+
+    rpc::notification("textDocument/publishDiagnostics", Json::object([
+        ("uri", uri.into()),
+        ("diagnostics", diagnostics.into()),
+    ]))
+}
+
+/// Range on a line, `line` starts at 1.
+fn range(line: usize, columns: (usize, usize)) -> Json {
+    let position = |character: usize| Json::object([
+        ("line", line.saturating_sub(1).into()),
+        ("character", character.into()),
+    ]);
+
+    Json::object([("start", position(columns.0)), ("end", position(columns.1))])
+}
+
+fn format_value(value: Value) -> String {
+    match value {
+        Value::U8(v) => format!("0x{v:02X} ({v})"),
+        Value::U16(v) => format!("0x{v:04X} ({v})"),
+        _ => String::new(),
+    }
+}
+
+/// Identifier around a position of a line, in UTF-16 code units.
+/// Repeat counts of macro calls are not part of the identifier.
+pub fn word_at(line: &str, character: usize) -> Option<String> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let character = line.chars().scan(0, |units, c| { *units += c.len_utf16(); Some(*units) })
+        .take_while(|units| *units <= character)
+        .count();
+
+    let is_ident = |c: &char| text::is_char_ident_first(*c) || text::is_char_num(*c);
+
+    let start = chars[..character.min(chars.len())].iter().rev().take_while(|c| is_ident(c)).count();
+    let end = chars.get(character..).unwrap_or_default().iter().take_while(|c| is_ident(c)).count();
+
+    let word = chars[character.min(chars.len()) - start..character.min(chars.len()) + end]
+        .iter()
+        .skip_while(|c| text::is_char_num(**c))
+        .collect::<String>();
+
+    (!word.is_empty()).then_some(word)
+}
+
+/// Position in UTF-16 code units of the character at `column` of a line.
+/// Columns past the end of the line, as for a file that cannot be read, are kept.
+fn utf16(line: &str, column: usize) -> usize {
+    line.chars().take(column).map(char::len_utf16).sum::<usize>() + column.saturating_sub(line.chars().count())
+}
+
+fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut bytes = vec![];
+    let mut iter = path.bytes();
+
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next().unwrap_or_default(), iter.next().unwrap_or_default()];
+
+            if let Some(decoded) = std::str::from_utf8(&hex).ok().and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                bytes.push(decoded);
+                continue;
+            }
+        }
+
+        bytes.push(b);
+    }
+
+    String::from_utf8_lossy(&bytes).into()
+}
+
+fn path_to_uri(path: &str) -> String {
+    let path = std::fs::canonicalize(path).map_or_else(|_| path.into(), |p| p.to_string_lossy().to_string());
+    let mut uri = String::from("file://");
+
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'/'|b'-'|b'_'|b'.'|b'~') {
+            uri.push(b as char);
+        }else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+
+    uri
+}
@@ -0,0 +1,54 @@
+// <https://microsoft.github.io/language-server-protocol/specifications/base/0.9/specification/>
+
+use crate::lsp::json::Json;
+
+use std::io::{ self, BufRead, Write };
+
+/// Reads the content of the next message, `None` at the end of the input.
+pub fn read(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut length = None;
+
+    // Headers end with an empty line.
+    loop {
+        let mut header = String::new();
+
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let length = length.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header"))?;
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+
+    String::from_utf8(content).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write(output: &mut impl Write, message: &Json) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    output.flush()
+}
+
+pub fn response(id: Json, result: Json) -> Json {
+    Json::object([("jsonrpc", "2.0".into()), ("id", id), ("result", result)])
+}
+
+pub fn error(id: Json, code: i32, message: &str) -> Json {
+    let error = Json::object([("code", Json::Number(code as f64)), ("message", message.into())]);
+    Json::object([("jsonrpc", "2.0".into()), ("id", id), ("error", error)])
+}
+
+pub fn notification(method: &str, params: Json) -> Json {
+    Json::object([("jsonrpc", "2.0".into()), ("method", method.into()), ("params", params)])
+}
@@ -0,0 +1,142 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io,
+    time::SystemTime,
+};
+
+use crate::parse::{ library, split::SplitSpans, provider::{ Provider, Disk } };
+
+/// Files read during a build.
+/// In watch mode they are kept between builds, so unchanged files are not read or split again.
+#[derive(Debug)]
+pub struct Files {
+    cache: RefCell<HashMap<String, CachedFile>>,
+    /// Content used instead of the file of the provider, such as unsaved changes in an editor.
+    overlays: RefCell<HashMap<String, String>>,
+    provider: Box<dyn Provider>,
+}
+
+impl Default for Files {
+    fn default() -> Self {
+        Self::new(Disk)
+    }
+}
+
+#[derive(Debug)]
+struct CachedFile {
+    /// `None` if the file could not be read.
+    modified: Option<SystemTime>,
+    data: Option<Vec<u8>>,
+    spans: Option<SplitSpans>,
+}
+
+impl Files {
+
+    /// Files read from `provider`, such as the disk or memory.
+    pub fn new(provider: impl Provider + 'static) -> Self {
+        Self{ cache: RefCell::default(), overlays: RefCell::default(), provider: Box::new(provider) }
+    }
+
+    /// Reads a file, from the standard library or from the provider.
+    /// The cached content is used if the file was not modified since the last read.
+    pub fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        if let Some(content) = library::get(path) {
+            self.cache.borrow_mut().entry(path.into()).or_insert_with(|| CachedFile{
+                modified: None,
+                data: Some(content.as_bytes().to_vec()),
+                spans: None,
+            });
+
+            return Ok(content.as_bytes().to_vec());
+        }
+
+        if let Some(content) = self.overlays.borrow().get(path) {
+            self.cache.borrow_mut().entry(path.into()).or_insert_with(|| CachedFile{
+                modified: None,
+                data: Some(content.as_bytes().to_vec()),
+                spans: None,
+            });
+
+            return Ok(content.as_bytes().to_vec());
+        }
+
+        let modified = self.provider.modified(path);
+        let mut cache = self.cache.borrow_mut();
+
+        if let Some(file) = cache.get(path) {
+            if file.modified.is_some() && file.modified == modified {
+                if let Some(data) = &file.data {
+                    return Ok(data.clone());
+                }
+            }
+        }
+
+        let data = self.provider.read(path);
+        let file = CachedFile{ modified, data: data.as_ref().ok().cloned(), spans: None };
+        cache.insert(path.into(), file);
+
+        data
+    }
+
+    /// Can the file be read?
+    /// A missing file is remembered as a change once it is created, such as an import not found yet.
+    pub fn exists(&self, path: &str) -> bool {
+        if library::get(path).is_some() || self.overlays.borrow().contains_key(path) {
+            return true;
+        }
+
+        let exists = self.provider.exists(path);
+
+        if !exists {
+            self.cache.borrow_mut().entry(path.into()).or_insert_with(|| CachedFile{
+                modified: None,
+                data: None,
+                spans: None,
+            });
+        }
+
+        exists
+    }
+
+    pub fn read_to_string(&self, path: &str) -> io::Result<String> {
+        String::from_utf8(self.read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Replaces the content of a file until the overlay is removed.
+    pub fn set_overlay(&self, path: &str, content: String) {
+        self.cache.borrow_mut().remove(path);
+        self.overlays.borrow_mut().insert(path.into(), content);
+    }
+
+    pub fn remove_overlay(&self, path: &str) {
+        self.cache.borrow_mut().remove(path);
+        self.overlays.borrow_mut().remove(path);
+    }
+
+    /// Has any file been modified, created or deleted since it was read?
+    pub fn changed(&self) -> bool {
+        let overlays = self.overlays.borrow();
+
+        self.cache.borrow().iter().any(|(path, file)| {
+            !library::is_library(path) && !overlays.contains_key(path) && self.provider.modified(path) != file.modified
+        })
+    }
+
+    /// Number of files read from the provider.
+    pub fn count(&self) -> usize {
+        self.cache.borrow().iter().filter(|(path, file)| !library::is_library(path) && file.data.is_some()).count()
+    }
+
+    /// Spans of the last split of a file, if it has not changed since.
+    pub fn spans(&self, path: &str) -> Option<SplitSpans> {
+        self.cache.borrow().get(path).and_then(|file| file.spans.clone())
+    }
+
+    pub fn keep_spans(&self, path: &str, spans: SplitSpans) {
+        if let Some(file) = self.cache.borrow_mut().get_mut(path) {
+            file.spans = Some(spans);
+        }
+    }
+
+}
@@ -106,12 +106,36 @@ pub enum TokenType {
                 BinOr,
                 BinXor,
                 UnNot,
+                BinEq,
+                BinNe,
+                BinLt,
+                BinLe,
+                BinGt,
+                BinGe,
             Identifier,
     Directive,
         DefB,
         DefW,
         Include,
         Import,
+        Rle,
+        Lz,
+        Cycles,
+        Assert,
+        Print,
+        Warn,
+        Error,
+        Struct,
+        Ram,
+        Var,
+        Enum,
+        DataB,
+        DataW,
+        Fill,
+        Load,
+        EndLoad,
+        Align,
+        Aligned,
         Macro,
             MacroIdent,
             MacroArg,
@@ -149,9 +173,11 @@ impl TokenType {
             FlagZ|FlagNz|FlagC|FlagNc => Flag,
 
             BinAdd|BinSub|BinMul|BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|
-            BinXor|UnNot => Expr,
+            BinXor|UnNot|BinEq|BinNe|BinLt|BinLe|BinGt|BinGe => Expr,
 
-            DefB|DefW|Include|Import|Macro => Directive,
+            DefB|DefW|Include|Import|Rle|Lz|Cycles|Assert|Print|Warn|Error|
+            Struct|Ram|Var|Enum|DataB|DataW|Fill|Load|EndLoad|Align|Aligned|
+            Macro => Directive,
 
             MacroIdent|MacroArg|MacroBody => Macro,
 
@@ -170,7 +196,9 @@ impl TokenType {
     pub const fn ends_on_newline(self) -> bool {
         matches!(self,
             Instruction|Argument|MacroCall|Directive|Marker|Expr|DefB|DefW|
-            Include|Import|Macro|NamedMark|AnonMark|Label)
+            Include|Import|Rle|Lz|Cycles|Assert|Print|Warn|Error|Struct|
+            Ram|Var|Enum|DataB|DataW|Fill|Load|EndLoad|Align|Aligned|Macro|
+            NamedMark|AnonMark|Label)
     }
 
     /// Find a token type that can be identified from a word.
@@ -249,6 +277,12 @@ impl TokenType {
             "NOT" => Some(UnNot),
             "SHL" => Some(BinShl),
             "SHR" => Some(BinShr),
+            "EQ" => Some(BinEq),
+            "NE" => Some(BinNe),
+            "LT" => Some(BinLt),
+            "LE" => Some(BinLe),
+            "GT" => Some(BinGt),
+            "GE" => Some(BinGe),
             "(" => Some(At0),
             ")" => Some(At1),
             "*" => Some(BinMul),
@@ -259,6 +293,21 @@ impl TokenType {
         }
     }
 
+    /// Every word accepted by `get_by_word`.
+    pub const WORDS: &'static [&'static str] = &[
+        "adc", "add", "and", "bit", "call", "ccf", "cp", "cpl",
+        "daa", "dec", "di", "ei", "halt", "inc", "jp", "jr",
+        "ld", "ldh", "ldi", "ldd", "ldhl", "or", "pop", "push",
+        "res", "ret", "rl", "rla", "rlc", "rld", "rr", "rra",
+        "rrc", "rrca", "rrd", "rst", "sbc", "scf", "set", "sla",
+        "sll", "sra", "srl", "stop", "sub", "swap", "xor", "reti",
+        "rlca", "nop", "a", "b", "c", "d", "e", "h",
+        "l", "af", "bc", "de", "hl", "sp", "Z", "NZ",
+        "C", "NC", "MOD", "AND", "OR", "XOR", "NOT", "SHL",
+        "SHR", "EQ", "NE", "LT", "LE", "GT", "GE", "(",
+        ")", "*", "/", "+", "-",
+    ];
+
     /// Is there any type that starts with this prefix character?
     pub const fn has_prefix(prefix: char) -> bool {
         matches!(prefix, '&'|'#'|'%'|'"'|'.'|':')
@@ -267,34 +316,41 @@ impl TokenType {
     /// Checks if the token has a valid parent.
     pub fn validate(self, parent_type: Self) -> bool {
         match self {
-            Directive|DefB|DefW|Include|Import|Macro|InstrName|Adc|Add|And|
-            Bit|Call|Ccf|Cp|Cpl|Daa|Dec|Di|Ei|Halt|Inc|Jp|Jr|Ld|Ldh|Ldi|
-            Ldd|Ldhl|Or|Pop|Push|Res|Ret|Rl|Rla|Rlc|Rld|Rr|Rra|Rrc|Rrca|
-            Rrd|Rst|Sbc|Scf|Set|Sla|Sll|Sra|Srl|Stop|Sub|Swap|Xor|Reti|Rlca|
-            Nop|Argument|A|B|C|D|E|H|L|Af|Bc|De|Hl|Sp|Flag|FlagZ|FlagNz|
-            FlagC|FlagNc|LitBin|LitHex|LitDec|LitStr|Marker|NamedMark|AnonMark|
-            Label|Repeat|MacroCall => self.parent_type() == parent_type,
+            DefB|DefW|Include|Import|Rle|Lz|Cycles|Assert|Print|Warn|Error|
+            Struct|Ram|Var|Enum|DataB|DataW|Fill|Load|EndLoad|Align|Aligned|
+            Macro|InstrName|Adc|Add|And|Bit|Call|Ccf|Cp|Cpl|Daa|Dec|Di|Ei|
+            Halt|Inc|Jp|Jr|Ld|Ldh|Ldi|Ldd|Ldhl|Or|Pop|Push|Res|Ret|Rl|Rla|
+            Rlc|Rld|Rr|Rra|Rrc|Rrca|Rrd|Rst|Sbc|Scf|Set|Sla|Sll|Sra|Srl|
+            Stop|Sub|Swap|Xor|Reti|Rlca|Nop|Argument|A|B|C|D|E|H|L|Af|Bc|
+            De|Hl|Sp|Flag|FlagZ|FlagNz|FlagC|FlagNc|LitBin|LitHex|LitDec|
+            LitStr|Marker|NamedMark|AnonMark|Label|Repeat|MacroCall => self.parent_type() == parent_type,
 
-            Instruction=> matches!(parent_type, Root|MacroBody),
+            Instruction|Directive=> matches!(parent_type, Root|MacroBody),
 
             Register=> matches!(parent_type, Argument|At|MacroCall|MacroBody),
 
-            Expr=> matches!(parent_type, DefB|DefW),
+            Expr=> matches!(parent_type, DefB|DefW|Assert|Print|Warn|Error|Ram|
+                Load),
 
             BinAdd|BinSub|BinMul|BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|
-            BinXor|UnNot=> matches!(parent_type, Expr|At|BinAdd|BinSub|BinMul|BinDiv|
-                BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot),
+            BinXor|UnNot|BinEq|BinNe|BinLt|BinLe|BinGt|BinGe=> matches!(parent_type, Expr|At|BinAdd|BinSub|BinMul|BinDiv|
+                BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|BinEq|BinNe|BinLt|
+                BinLe|BinGt|BinGe),
 
             At=> matches!(parent_type, Argument|Expr|At|BinAdd|BinSub|BinMul|
-                BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|MacroCall),
+                BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|BinEq|
+                BinNe|BinLt|BinLe|BinGt|BinGe|Enum|DataB|DataW|Fill|MacroCall),
 
             Lit=> matches!(parent_type, Argument|Expr|At|BinAdd|BinSub|BinMul|
-                BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|Root|NamedMark|
-                AnonMark|Include|MacroCall|MacroBody),
+                BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|BinEq|
+                BinNe|BinLt|BinLe|BinGt|BinGe|Root|NamedMark|AnonMark|Include|
+                Rle|Lz|Cycles|Assert|Print|Warn|Error|Struct|Var|Enum|DataB|
+                DataW|Fill|Align|Aligned|MacroCall|MacroBody),
 
-            Identifier=> matches!(parent_type, DefB|DefW|Argument|Root|At|Expr|BinAdd|
-                BinSub|BinMul|BinDiv|BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|
-                UnNot|MacroCall|MacroBody),
+            Identifier=> matches!(parent_type, DefB|DefW|Rle|Lz|Struct|Var|Enum|DataB|
+                DataW|Fill|Load|Argument|Root|At|Expr|BinAdd|BinSub|BinMul|BinDiv|
+                BinMod|BinShr|BinShl|BinAnd|BinOr|BinXor|UnNot|BinEq|BinNe|BinLt|
+                BinLe|BinGt|BinGe|MacroCall|MacroBody),
 
             MacroIdent|MacroBody=> matches!(parent_type, MacroCall),
 
@@ -306,7 +362,7 @@ impl TokenType {
 
     /// The count of token types.
     #[cfg(test)]
-    pub const COUNT: usize = 107;
+    pub const COUNT: usize = 131;
 
     /// Returns a `TokenType` from an index.
     #[cfg(test)]
@@ -403,22 +459,46 @@ impl TokenType {
             88 => BinOr,
             89 => BinXor,
             90 => UnNot,
-            91 => Identifier,
-            92 => Directive,
-            93 => DefB,
-            94 => DefW,
-            95 => Include,
-            96 => Import,
-            97 => Macro,
-            98 => MacroIdent,
-            99 => MacroArg,
-            100 => MacroBody,
-            101 => Marker,
-            102 => NamedMark,
-            103 => AnonMark,
-            104 => Label,
-            105 => Repeat,
-            106 => MacroCall,
+            91 => BinEq,
+            92 => BinNe,
+            93 => BinLt,
+            94 => BinLe,
+            95 => BinGt,
+            96 => BinGe,
+            97 => Identifier,
+            98 => Directive,
+            99 => DefB,
+            100 => DefW,
+            101 => Include,
+            102 => Import,
+            103 => Rle,
+            104 => Lz,
+            105 => Cycles,
+            106 => Assert,
+            107 => Print,
+            108 => Warn,
+            109 => Error,
+            110 => Struct,
+            111 => Ram,
+            112 => Var,
+            113 => Enum,
+            114 => DataB,
+            115 => DataW,
+            116 => Fill,
+            117 => Load,
+            118 => EndLoad,
+            119 => Align,
+            120 => Aligned,
+            121 => Macro,
+            122 => MacroIdent,
+            123 => MacroArg,
+            124 => MacroBody,
+            125 => Marker,
+            126 => NamedMark,
+            127 => AnonMark,
+            128 => Label,
+            129 => Repeat,
+            130 => MacroCall,
             _ => panic!()
         }
     }
@@ -0,0 +1,23 @@
+// Files of the standard library are embedded in the assembler.
+// They are imported with the `std:` prefix, for example `#import "std:hardware.gb.asm"`.
+
+/// Prefix of the paths to the standard library.
+pub const PREFIX: &str = "std:";
+
+const FILES: [(&str, &str); 5] = [
+    ("hardware.gb.asm", include_str!("../../std/hardware.gb.asm")),
+    ("memory.gb.asm", include_str!("../../std/memory.gb.asm")),
+    ("dma.gb.asm", include_str!("../../std/dma.gb.asm")),
+    ("joypad.gb.asm", include_str!("../../std/joypad.gb.asm")),
+    ("decompress.gb.asm", include_str!("../../std/decompress.gb.asm")),
+];
+
+/// Content of a file from the standard library, by its path with the prefix.
+pub fn get(path: &str) -> Option<&'static str> {
+    let name = path.strip_prefix(PREFIX)?;
+    FILES.iter().find(|(n, _)| *n == name).map(|(_, content)| *content)
+}
+
+pub fn is_library(path: &str) -> bool {
+    path.starts_with(PREFIX)
+}
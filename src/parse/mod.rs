@@ -2,6 +2,15 @@
 /// Gets the source assembly code.
 pub mod source;
 
+/// Reads and caches the files used by a build.
+pub mod files;
+
+/// Reads files from the disk or from memory.
+pub mod provider;
+
+/// Standard library embedded in the assembler.
+pub mod library;
+
 /// Splits the input file into words.
 pub mod split;
 
@@ -5,11 +5,13 @@ use crate::{
         text::{self, CheckedStr},
         lex::TokenType::{self, *},
         split::Split,
+        source::Source,
     },
     error::{ ErrCtx, asm::{AsmErr, ParseMsg::{self, *}} },
 };
 
 /// Output of the parser. Contains the type and the key to the data.
+#[derive(Copy, Clone)]
 pub struct ParsedToken<'a> {
     pub ty: TokenType,
     pub value: Value<'a>,
@@ -20,13 +22,25 @@ pub struct ParsedToken<'a> {
 }
 
 /// Map words to token types and extract their data.
+#[allow(dead_code)]
 pub fn parse<'a>(
     split: &Split<'a>,
 ) -> Result<Vec<ParsedToken<'a>>, Vec<AsmErr<'a, ParseMsg>>> {
+    let (parsed_tokens, errors) = read(split);
+
+    if errors.is_empty() {
+        Ok(parsed_tokens)
+    }else {
+        Err(errors)
+    }
+}
+
+/// Parses the words, leaving out the tokens of the lines with errors.
+fn read<'a>(split: &Split<'a>) -> (Vec<ParsedToken<'a>>, Vec<AsmErr<'a, ParseMsg>>) {
     let mut parsed_tokens = vec![];
     let mut errors = vec![];
     let words_vec = split.words();
-    let mut words = words_vec.iter();
+    let mut words = words_vec.iter().peekable();
 
     while let Some((word, line, line_number, file)) = words.next() {
         // Attempt to identify the token. 
@@ -43,8 +57,11 @@ pub fn parse<'a>(
 
         let mut id_words = id_words.unwrap();
 
-        if matches!(id_words[0].0, DefB|DefW) {
-            if let Some((word, line, line_number, file)) = words.next() {
+        // The name of the constant is on the line of its directive.
+        if matches!(id_words[0].0, DefB|DefW|Rle|Lz) {
+            let same_line = |(_, _, next, next_file): &&_| *next == line_number && next_file == file;
+
+            if let Some((word, line, line_number, file)) = words.next_if(same_line) {
                 let line_number = *line_number;
                 let mut is_ident = false;
                 let mut is_allowed = false;
@@ -102,15 +119,85 @@ pub fn parse<'a>(
         }
     }
 
-    if errors.is_empty() {
-        Ok(parsed_tokens)
-    }else {
-        Err(errors)
+    parsed_tokens.retain(|token| errors.iter().all(|e| e.err_ctx.line_number() != token.line_number));
+    (parsed_tokens, errors)
+}
+
+/// Parses the words of every input and links them.
+/// The tokens of lines with errors are left out, so that the other lines can still be built.
+pub fn parse_all<'a>(
+    source: &'a Source,
+    splits: &[Split<'a>],
+) -> (Vec<ParsedToken<'a>>, Vec<AsmErr<'a, ParseMsg>>) {
+    let mut parsed = vec![];
+    let mut errors = vec![];
+
+    for split in splits {
+        let (tokens, mut e) = read(split);
+        parsed.push(tokens);
+        errors.append(&mut e);
+    }
+
+    let (linked, mut e) = link(source, parsed);
+    errors.append(&mut e);
+
+    (linked, errors)
+}
+
+/// Replaces `#import` directives with the tokens of the imported files.
+/// `parsed` holds the tokens of each input of the `Source`, in the same order.
+/// A file is only imported once, further imports of the same file are ignored.
+fn link<'a>(
+    source: &'a Source,
+    parsed: Vec<Vec<ParsedToken<'a>>>,
+) -> (Vec<ParsedToken<'a>>, Vec<AsmErr<'a, ParseMsg>>) {
+    let mut parsed = parsed.into_iter().map(Some).collect::<Vec<_>>();
+    let mut linked = vec![];
+    let mut errors = vec![];
+
+    link_walk(source, &mut parsed, 0, &mut linked, &mut errors);
+    (linked, errors)
+}
+
+fn link_walk<'a>(
+    source: &'a Source,
+    parsed: &mut [Option<Vec<ParsedToken<'a>>>],
+    index: usize,
+    linked: &mut Vec<ParsedToken<'a>>,
+    errors: &mut Vec<AsmErr<'a, ParseMsg>>,
+) {
+    let Some(tokens) = parsed[index].take() else {
+        return;
+    };
+
+    let input = &source.inputs[index];
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        if token.ty != Import {
+            linked.push(token);
+            continue;
+        }
+
+        // The path must follow on the same line.
+        let path = tokens.next_if(|t| t.ty == LitStr && t.line_number == token.line_number);
+
+        let Some(path) = path else {
+            errors.push(err!(ParseMsg, BadImport, (&token).into()));
+            continue;
+        };
+
+        let local = path.value.as_str().unwrap();
+
+        match source.resolve(input, local).and_then(|path| source.position(&path)) {
+            Some(import) => link_walk(source, parsed, import, linked, errors),
+            None => errors.push(err!(ParseMsg, ImportNotFound, (&path).into())),
+        }
     }
 }
 
 /// Extract the data from a word.
-fn extract(word: (TokenType, &str)) -> Result<(TokenType, Value), ParseMsg> {
+fn extract(word: (TokenType, &str)) -> Result<(TokenType, Value<'_>), ParseMsg> {
     let (ty, str_value) = word; 
 
     // There is no value to extract.
@@ -180,12 +267,12 @@ fn extract(word: (TokenType, &str)) -> Result<(TokenType, Value), ParseMsg> {
             Ok((ty, Value::Str(str_value)))
         }
 
-        _ => bug!("Unhandled type")
+        _ => Err(ParseMsg::Internal),
     }
 }
 
 /// Get token type(s) and value(s) from word.
-fn identify(word: &str) -> Result<Vec<(TokenType, CheckedStr)>, ParseMsg> {
+fn identify(word: &str) -> Result<Vec<(TokenType, CheckedStr<'_>)>, ParseMsg> {
     if word.is_empty() {
         return Err(ParseMsg::EmptyStr);
     }
@@ -249,6 +336,24 @@ fn identify(word: &str) -> Result<Vec<(TokenType, CheckedStr)>, ParseMsg> {
                     "dw" => Ok(vec![ (DefW, text::no_check(directive)) ]),
                     "include" => Ok(vec![ (Include, text::no_check(directive)) ]),
                     "import" => Ok(vec![ (Import, text::no_check(directive)) ]),
+                    "rle" => Ok(vec![ (Rle, text::no_check(directive)) ]),
+                    "lz" => Ok(vec![ (Lz, text::no_check(directive)) ]),
+                    "cycles" => Ok(vec![ (Cycles, text::no_check(directive)) ]),
+                    "assert" => Ok(vec![ (Assert, text::no_check(directive)) ]),
+                    "print" => Ok(vec![ (Print, text::no_check(directive)) ]),
+                    "warn" => Ok(vec![ (Warn, text::no_check(directive)) ]),
+                    "error" => Ok(vec![ (Error, text::no_check(directive)) ]),
+                    "struct" => Ok(vec![ (Struct, text::no_check(directive)) ]),
+                    "ram" => Ok(vec![ (Ram, text::no_check(directive)) ]),
+                    "var" => Ok(vec![ (Var, text::no_check(directive)) ]),
+                    "enum" => Ok(vec![ (Enum, text::no_check(directive)) ]),
+                    "byte" => Ok(vec![ (DataB, text::no_check(directive)) ]),
+                    "word" => Ok(vec![ (DataW, text::no_check(directive)) ]),
+                    "fill" => Ok(vec![ (Fill, text::no_check(directive)) ]),
+                    "load" => Ok(vec![ (Load, text::no_check(directive)) ]),
+                    "endload" => Ok(vec![ (EndLoad, text::no_check(directive)) ]),
+                    "align" => Ok(vec![ (Align, text::no_check(directive)) ]),
+                    "aligned" => Ok(vec![ (Aligned, text::no_check(directive)) ]),
                     "macro" => Ok(vec![ (Macro, text::no_check(directive)) ]),
                     _ => Err(ParseMsg::BadDirectiveIdent)
                 };
@@ -268,7 +373,7 @@ fn identify(word: &str) -> Result<Vec<(TokenType, CheckedStr)>, ParseMsg> {
             }
 
             // Search by prefix gave a wrong result
-            _ => bug!("Unexpected prefix")
+            _ => return Err(ParseMsg::Internal),
         }
     }
 
@@ -318,7 +423,7 @@ fn identify(word: &str) -> Result<Vec<(TokenType, CheckedStr)>, ParseMsg> {
 
 /// Fits a number into a `Value` based on the literal's length (&00FF would be a u16).
 fn fit(num: usize, len: usize, base: usize) -> Result<Value<'static>, ParseMsg> {
-    return match base {
+    match base {
         2 => if len > 16 {
             Err(ParseMsg::BadBin) 
         }else if len > 8 {
@@ -343,6 +448,6 @@ fn fit(num: usize, len: usize, base: usize) -> Result<Value<'static>, ParseMsg>
             Ok(Value::U8(num as u8))
         }
 
-        _ => bug!("Bad base."),
-    };
+        _ => Err(ParseMsg::Internal),
+    }
 }
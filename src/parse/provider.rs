@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::Path,
+    time::SystemTime,
+};
+
+/// Where the files of a build are read from: the main file, imports and included binaries.
+pub trait Provider {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    fn exists(&self, path: &str) -> bool;
+
+    /// Time of the last change, to read a cached file again once it changed.
+    /// `None` if it is unknown, the file is then read for every build.
+    fn modified(&self, _path: &str) -> Option<SystemTime> {
+        None
+    }
+}
+
+impl std::fmt::Debug for dyn Provider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Provider")
+    }
+}
+
+/// Files on the disk.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Disk;
+
+impl Provider for Disk {
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        Path::new(path).is_file()
+    }
+
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+
+}
+
+/// Files in memory, by path.
+#[derive(Debug, Default, Clone)]
+pub struct Memory {
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl Memory {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, replacing the one at the same path.
+    pub fn with(mut self, path: &str, content: impl Into<Vec<u8>>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    pub fn insert(&mut self, path: &str, content: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+}
+
+impl Provider for Memory {
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.files.get(path).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+
+}
+
+/// Reads the files with a function, that returns `None` when a file does not exist.
+impl<F: Fn(&str) -> Option<Vec<u8>>> Provider for F {
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self(path).ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self(path).is_some()
+    }
+
+}
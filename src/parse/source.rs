@@ -1,28 +1,109 @@
 
 use std::{
     path::Path,
-    fs::{self, File},
-    io::{self, prelude::*},
+    rc::Rc,
+    io,
 };
 
+use crate::parse::{ library, files::Files };
+
 #[derive(Debug)]
 pub struct Source {
     pub inputs: Vec<Input>,
+    /// Directories searched for files that are not next to the file including them.
+    pub search_paths: Vec<String>,
+    /// Every file read while building.
+    pub files: Rc<Files>,
 }
 
 impl Source {
     
-    pub fn new(main_path: &str) -> std::io::Result<Self> {
-        let main_content = fs::read_to_string(main_path)?;
-        let mut source = Self{ inputs: vec![] };
+    /// Reads the source through files that may already be cached.
+    pub fn new(
+        main_path: &str,
+        search_paths: &[&str],
+        files: Rc<Files>,
+    ) -> std::io::Result<Self> {
+        let main_content = files.read_to_string(main_path)?;
+
+        let mut source = Self{ 
+            inputs: vec![],
+            search_paths: search_paths.iter().map(|p| p.to_string()).collect(),
+            files,
+        };
+
         source.inputs.push(Input::new(main_path.into(), main_content));
+        source.load_imports(0);
 
         Ok(source)
     }
 
+    /// Reads the files imported by an input, recursively.
+    /// Files that cannot be read are skipped, the error is reported when linking.
+    fn load_imports(&mut self, index: usize) {
+        let input = &self.inputs[index];
+        let paths = input.imports().filter_map(|local| self.resolve(input, local)).collect::<Vec<_>>();
+
+        for path in paths {
+            if self.get(&path).is_some() {
+                continue;
+            }
+
+            if let Ok(content) = self.files.read_to_string(&path) {
+                self.inputs.push(Input::new(path, content));
+                self.load_imports(self.inputs.len() - 1);
+            }
+        }
+    }
+
+    /// Finds the path of a file included from an input.
+    /// Paths with the prefix of the standard library, or included from it, point to the library.
+    /// Others are relative to the input, then to each search path in order.
+    pub fn resolve(&self, input: &Input, local: &str) -> Option<String> {
+        if library::is_library(local) || library::is_library(&input.path) {
+            let path = if library::is_library(local) {
+                local.into()
+            }else {
+                format!("{}{local}", library::PREFIX)
+            };
+
+            return library::get(&path).map(|_| path);
+        }
+
+        let relative = input.local_path(local);
+
+        if self.files.exists(&relative) {
+            return Some(relative);
+        }
+
+        self.search_paths.iter()
+            .map(|dir| format!("{}/{local}", dir.trim_end_matches('/')))
+            .find(|path| self.files.exists(path))
+    }
+
+    /// Reads a file included from an input to bytes.
+    /// Returns the resolved path and the content.
+    pub fn read(&self, input: &Input, local: &str) -> Result<(String, Vec<u8>), io::Error> {
+        let path = self.resolve(input, local).ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+        let data = self.files.read(&path)?;
+
+        Ok((path, data))
+    }
+
+    /// Finds an input by its path.
+    pub fn get(&self, path: &str) -> Option<&Input> {
+        self.inputs.iter().find(|input| input.path == path)
+    }
+
+    /// Index of an input in `inputs`.
+    pub fn position(&self, path: &str) -> Option<usize> {
+        self.inputs.iter().position(|input| input.path == path)
+    }
+
     #[cfg(test)]
     pub fn from_content(content: String) -> Self {
-        let mut source = Self{ inputs: vec![] };
+        let mut source = Self{ inputs: vec![], search_paths: vec![], files: Rc::default() };
         source.inputs.push(Input::new(String::new(), content));
 
         source
@@ -51,23 +132,24 @@ impl Input {
         Path::new(&self.path)
     }
 
-    /// Reads a file to bytes with a relative path.
-    pub fn read_local(&self, local: &str) -> Result<Vec<u8>, io::Error> {
-        let path = self.path().parent().map_or_else(|| {
-            local.into() 
-        }, |dir| {
-            format!("{}/{}", dir.to_str().unwrap(), local)
-        });
-
-        let mut buffer = vec![];
-        let mut file = File::open(path)?;
-        file.read_to_end(&mut buffer)?;
-
-        Ok(buffer)
+    /// Path of a file relative to this one.
+    pub fn local_path(&self, local: &str) -> String {
+        match self.path().parent().and_then(Path::to_str) {
+            Some(dir) if !dir.is_empty() => format!("{dir}/{local}"),
+            _ => local.into(),
+        }
     }
 
-    pub fn lines(&self) -> std::str::Lines {
+    pub fn lines(&self) -> std::str::Lines<'_> {
         self.content.lines() 
     }
 
+    /// Paths from the `#import` directives, as they are written.
+    pub fn imports(&self) -> impl Iterator<Item = &str> {
+        self.lines().filter_map(|line| {
+            let path = line.trim_start().strip_prefix("#import")?.trim_start().strip_prefix('"')?;
+            path.get(..path.find('"')?)
+        })
+    }
+
 }
@@ -1,13 +1,15 @@
 
 use crate::{
-    parse::{ lex, source::Input },
+    parse::{ lex, source::{ Source, Input } },
     error::init::{SplitErr, SplitErrType},
 };
 
 #[cfg(debug_assertions)]
 use crate::program::fmt::title;
 
-#[derive(Copy, Clone)]
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug)]
 pub struct LineIndex {
     value: usize,
 }
@@ -50,11 +52,56 @@ impl<'a> Split<'a> {
         self.words.iter().map(|w| w.read(self)).collect::<Vec<_>>()
     }
 
+    /// Splits all the inputs of the source, in the same order.
+    /// The words of lines with errors are left out, so that the other lines can still be built.
+    pub fn all(
+        source: &'a Source,
+        symbols: &[&'a str],
+    ) -> (Vec<Split<'a>>, Vec<SplitErr<'a>>) {
+        let mut splits = vec![];
+        let mut errors = vec![];
+
+        for input in &source.inputs {
+            // The file did not change since the last build.
+            if let Some(spans) = source.files.spans(&input.path) {
+                splits.push(spans.split(input));
+                continue;
+            }
+
+            let (split, mut e) = Self::read(input, symbols);
+
+            // Files with errors are read again by the next build.
+            if e.is_empty() {
+                source.files.keep_spans(&input.path, split.spans(input));
+            }
+
+            splits.push(split);
+            errors.append(&mut e);
+        }
+
+        (splits, errors)
+    }
+
     /// Split source file into lines and words.
+    #[allow(dead_code)]
     pub fn new(
         input: &'a Input,
         symbols: &[&'a str],
     ) -> Result<Split<'a>, Vec<SplitErr<'a>>> {
+        let (split, errors) = Self::read(input, symbols);
+
+        if errors.is_empty() {
+            Ok(split)
+        }else {
+            Err(errors)
+        }
+    }
+
+    /// Splits the input, without the words of the lines with errors.
+    fn read(
+        input: &'a Input,
+        symbols: &[&'a str],
+    ) -> (Split<'a>, Vec<SplitErr<'a>>) {
         let mut errors = vec![];
 
         let mut str_literal = false; 
@@ -74,14 +121,19 @@ impl<'a> Split<'a> {
         };
 
         for (l_i, line) in input.lines().enumerate() {
-            for (c_i, ch) in line.chars().enumerate() {
+            // Lines skipped by a condition are ignored, apart from the conditions.
+            if !splitter.process && !matches!(line.split_whitespace().next(), Some("#if"|"#else"|"#endif")) {
+                continue;
+            }
+
+            for (c_i, ch) in line.char_indices() {
                 // String literal.
                  if ch == '"' {
                     // Push current word, or the string literal that just ended.
                     if has_word {
                         line.get(word_start..c_i).map_or_else(|| {
                             errors.push(SplitErr::new(
-                                SplitErrType::BadWord, line, l_i + 1));
+                                SplitErrType::BadWord, &input.path, line, l_i + 1));
                         }, |word| {
                             splitter.push(word, l_i);
                             has_word = false;
@@ -97,7 +149,7 @@ impl<'a> Split<'a> {
                     else {
                         line.get(word_start..c_i).map_or_else(|| {
                             errors.push(SplitErr::new(
-                                SplitErrType::BadWord, line, l_i + 1));
+                                SplitErrType::BadWord, &input.path, line, l_i + 1));
                         }, |word| {
                             splitter.push(word, l_i);
                         });
@@ -114,7 +166,7 @@ impl<'a> Split<'a> {
                     if has_word {
                         line.get(word_start..c_i).map_or_else(|| {
                             errors.push(SplitErr::new(
-                                SplitErrType::BadWord, line, l_i + 1));
+                                SplitErrType::BadWord, &input.path, line, l_i + 1));
                         }, |word| {
                             splitter.push(word, l_i);
                             has_word = false;
@@ -135,7 +187,7 @@ impl<'a> Split<'a> {
 
                     else {
                         errors.push(SplitErr::new(
-                            SplitErrType::MisplacedDirective, line, l_i + 1));
+                            SplitErrType::MisplacedDirective, &input.path, line, l_i + 1));
                         break;
                     }
                 }
@@ -147,7 +199,7 @@ impl<'a> Split<'a> {
                         if has_word { 
                             line.get(word_start..c_i).map_or_else(|| {
                                 errors.push(SplitErr::new(
-                                    SplitErrType::BadWord, line, l_i + 1));
+                                    SplitErrType::BadWord, &input.path, line, l_i + 1));
                             }, |word| {
                                 splitter.push(word, l_i);
                                 has_word = false;
@@ -155,9 +207,9 @@ impl<'a> Split<'a> {
                         }
 
                         // Push character.
-                        line.get(c_i..=c_i).map_or_else(|| {
+                        line.get(c_i..c_i + ch.len_utf8()).map_or_else(|| {
                             errors.push(SplitErr::new(
-                                SplitErrType::BadWord, line, l_i + 1));
+                                SplitErrType::BadWord, &input.path, line, l_i + 1));
                         }, |word| {
                             splitter.push(word, l_i);
                         });
@@ -167,7 +219,7 @@ impl<'a> Split<'a> {
                         if has_word { 
                             line.get(word_start..c_i).map_or_else(|| {
                                 errors.push(SplitErr::new(
-                                    SplitErrType::BadWord, line, l_i + 1));
+                                    SplitErrType::BadWord, &input.path, line, l_i + 1));
                             }, |word| {
                                 splitter.push(word, l_i);
                                 has_word = false;
@@ -186,7 +238,7 @@ impl<'a> Split<'a> {
             if has_word { 
                 line.get(word_start..).map_or_else(|| {
                     errors.push(SplitErr::new(
-                        SplitErrType::BadWord, line, l_i + 1));
+                        SplitErrType::BadWord, &input.path, line, l_i + 1));
                 }, |word| {
                     splitter.push(word, l_i);
                     has_word = false;
@@ -216,7 +268,7 @@ impl<'a> Split<'a> {
                 }
 
                 else {
-                    errors.push(SplitErr::new(SplitErrType::BadDirective, line, l_i + 1));
+                    errors.push(SplitErr::new(SplitErrType::BadDirective, &input.path, line, l_i + 1));
                 }
 
                 splitter.directive.clear();
@@ -227,8 +279,24 @@ impl<'a> Split<'a> {
             dir_line = false;
         }
 
-        let Splitter{ lines, line_numbers, words, .. } = splitter;
-        Ok(Split { file: &input.path, lines, line_numbers, words })
+        let Splitter{ lines, line_numbers, mut words, .. } = splitter;
+        words.retain(|w| errors.iter().all(|e| e.line_number() != line_numbers[w.line_index.value]));
+
+        (Split { file: &input.path, lines, line_numbers, words }, errors)
+    }
+
+    /// Positions of the lines and words in the input.
+    pub fn spans(&self, input: &Input) -> SplitSpans {
+        let offset = |s: &str| {
+            let start = s.as_ptr() as usize - input.content.as_ptr() as usize;
+            start..start + s.len()
+        };
+
+        SplitSpans{
+            lines: self.lines.iter().map(|line| offset(line)).collect(),
+            line_numbers: self.line_numbers.clone(),
+            words: self.words.iter().map(|w| (w.line_index, offset(w.value))).collect(),
+        }
     }
 
     #[cfg(debug_assertions)]
@@ -258,6 +326,32 @@ impl<'a> Split<'a> {
 
 }
 
+/// A split that does not borrow its input, to be kept between builds.
+#[derive(Clone, Debug)]
+pub struct SplitSpans {
+    lines: Vec<Range<usize>>,
+    line_numbers: Vec<usize>,
+    words: Vec<(LineIndex, Range<usize>)>,
+}
+
+impl SplitSpans {
+
+    /// Rebuilds the split from the same input it was made from.
+    pub fn split<'a>(&self, input: &'a Input) -> Split<'a> {
+        let content = input.content.as_str();
+
+        Split{
+            file: &input.path,
+            lines: self.lines.iter().map(|r| &content[r.clone()]).collect(),
+            line_numbers: self.line_numbers.clone(),
+            words: self.words.iter().map(|(line_index, r)| {
+                Word{ line_index: *line_index, value: &content[r.clone()] }
+            }).collect(),
+        }
+    }
+
+}
+
 struct Splitter<'a> {
     words: Vec<Word<'a>>,
     lines : Vec<&'a str>,
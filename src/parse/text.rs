@@ -41,7 +41,7 @@ pub const fn is_char_ident_first(c: char) -> bool {
 }
 
 /// Is the word an identifier?
-pub fn check_ident(word: &str) -> Option<CheckedStr> {
+pub fn check_ident(word: &str) -> Option<CheckedStr<'_>> {
     let mut iter = word.chars();
 
     let first = iter.next();
@@ -59,7 +59,7 @@ pub fn check_ident(word: &str) -> Option<CheckedStr> {
 }
 
 /// Is the word a decimal literal?
-pub fn check_dec(word: &str) -> Option<CheckedStr> {
+pub fn check_dec(word: &str) -> Option<CheckedStr<'_>> {
     for c in word.chars() {
         if !is_char_num(c) { 
             return None
@@ -70,7 +70,7 @@ pub fn check_dec(word: &str) -> Option<CheckedStr> {
 }
 
 /// Is the word an hexadecimal literal?
-pub fn check_hex(word: &str) -> Option<CheckedStr> {
+pub fn check_hex(word: &str) -> Option<CheckedStr<'_>> {
     for c in word.chars() {
         if !is_char_hex(c) { 
             return None
@@ -81,7 +81,7 @@ pub fn check_hex(word: &str) -> Option<CheckedStr> {
 }
 
 /// Is the word an binary literal?
-pub fn check_bin(word: &str) -> Option<CheckedStr> {
+pub fn check_bin(word: &str) -> Option<CheckedStr<'_>> {
     for c in word.chars() {
         if c != '0' && c != '1' && c != '_' { 
             return None
@@ -93,6 +93,6 @@ pub fn check_bin(word: &str) -> Option<CheckedStr> {
 }
 
 /// Use for strings that do not need checking.
-pub const fn no_check(word: &str) -> CheckedStr {
+pub const fn no_check(word: &str) -> CheckedStr<'_> {
     CheckedStr::new(word)
 }
@@ -1,5 +1,7 @@
 
 use crate::{
+    token::lint::{ Lints, Level },
+    write::encode::Format,
     error::init::{ClargsErr, ClargsErrType::*},
 };
 
@@ -8,7 +10,21 @@ use crate::{
 pub struct Clargs<'a> {
     pub path: &'a str,
     pub symbols: Vec<&'a str>,
+    pub search_paths: Vec<&'a str>,
     pub output: Option<&'a str>,
+    pub deps: Option<&'a str>,
+    pub watch: bool,
+    pub lints: Lints,
+    pub listing: Option<&'a str>,
+    /// Labels to count the cycles between.
+    pub cycles: Vec<&'a str>,
+    /// Errors are written as JSON diagnostics.
+    pub json: bool,
+    pub format: Format,
+    /// Start and end of the addresses to write, the end is excluded.
+    pub range: Vec<usize>,
+    /// ROM the source is written over.
+    pub base: Option<&'a str>,
 }
 
 impl<'a> Clargs<'a> {
@@ -17,6 +33,10 @@ impl<'a> Clargs<'a> {
         self.output.map_or_else(|| { "./out.gb".into() }, |output| { output.into() })
     }
 
+    pub fn range(&self) -> Option<(usize, usize)> {
+        if let [start, end] = self.range[..] { Some((start, end)) }else{ None }
+    }
+
 }
 
 /// Get CLI arguments into a struct.
@@ -25,8 +45,20 @@ impl<'a> Clargs<'a> {
 /// source file
 /// -o                      Output file
 /// -D \[SYMBOLS\]            Defined symbols, optional.
-pub fn parse(args: &[String]) -> Result<Clargs, ClargsErr> {
-    enum Ty { Unknown, Define, Output }
+/// -I \[DIRECTORIES\]        Search paths for included files, optional.
+/// -M \[FILE\]               Dependency file for make, optional.
+/// -w                      Watch mode, optional.
+/// -A \[LINTS\]              Allowed lints, optional.
+/// -W \[LINTS\]              Lints reported as warnings, optional.
+/// -E \[LINTS\]              Lints reported as errors, optional.
+/// -l \[FILE\]               Listing file, optional.
+/// -c \[LABEL\] \[LABEL\]      Labels to count the cycles between, optional.
+/// --json                  Errors as JSON diagnostics, optional.
+/// -f \[FORMAT\]             Output format: gb, hex, bin, or ips and bps patches that need -b, optional.
+/// -r \[START\] \[END\]       Range of addresses to write, optional.
+/// -b \[FILE\]               Base ROM to write over, optional.
+pub fn parse(args: &[String]) -> Result<Clargs<'_>, ClargsErr<'_>> {
+    enum Ty { Unknown, Define, Search, Output, Deps, Lint(Level), Listing, Cycles, Format, Range, Base }
 
 
     if args.len() < 2 {
@@ -46,11 +78,32 @@ pub fn parse(args: &[String]) -> Result<Clargs, ClargsErr> {
     for arg in &args[2..] {
         match arg.as_str() {
             "-D" => ty = Ty::Define,
+            "-I" => ty = Ty::Search,
             "-o" => ty = Ty::Output,
+            "-M" => ty = Ty::Deps,
+            "-A" => ty = Ty::Lint(Level::Allow),
+            "-W" => ty = Ty::Lint(Level::Warn),
+            "-E" => ty = Ty::Lint(Level::Deny),
+            "-l" => ty = Ty::Listing,
+            "-c" => ty = Ty::Cycles,
+            "-f" => ty = Ty::Format,
+            "-r" => ty = Ty::Range,
+            "-b" => ty = Ty::Base,
+
+            "-w" => {
+                clargs.watch = true;
+                ty = Ty::Unknown;
+            }
+
+            "--json" => {
+                clargs.json = true;
+                ty = Ty::Unknown;
+            }
 
             _ => {
                 match ty {
                     Ty::Define => clargs.symbols.push(arg),
+                    Ty::Search => clargs.search_paths.push(arg),
 
                     Ty::Output => {
                         if clargs.output.is_none() {
@@ -60,6 +113,55 @@ pub fn parse(args: &[String]) -> Result<Clargs, ClargsErr> {
                         }
                     }
 
+                    Ty::Deps => {
+                        if clargs.deps.is_none() {
+                            clargs.deps = Some(arg);
+                        }else{
+                            return Err(ClargsErr::new(TooManyParams, arg));
+                        }
+                    }
+
+                    Ty::Listing => {
+                        if clargs.listing.is_none() {
+                            clargs.listing = Some(arg);
+                        }else{
+                            return Err(ClargsErr::new(TooManyParams, arg));
+                        }
+                    }
+
+                    Ty::Cycles => {
+                        if clargs.cycles.len() < 2 {
+                            clargs.cycles.push(arg);
+                        }else{
+                            return Err(ClargsErr::new(TooManyParams, arg));
+                        }
+                    }
+
+                    Ty::Format => {
+                        clargs.format = Format::from_name(arg).ok_or(ClargsErr::new(UnknownFormat, arg))?;
+                        ty = Ty::Unknown;
+                    }
+
+                    Ty::Range => {
+                        if clargs.range.len() < 2 {
+                            clargs.range.push(address(arg).ok_or(ClargsErr::new(BadAddress, arg))?);
+                        }else{
+                            return Err(ClargsErr::new(TooManyParams, arg));
+                        }
+                    }
+
+                    Ty::Base => {
+                        if clargs.base.is_none() {
+                            clargs.base = Some(arg);
+                        }else{
+                            return Err(ClargsErr::new(TooManyParams, arg));
+                        }
+                    }
+
+                    Ty::Lint(level) => {
+                        clargs.lints.set(arg, level).map_err(|_| ClargsErr::new(UnknownLint, arg))?;
+                    }
+
                     Ty::Unknown => return Err(ClargsErr::new(UnknownArg, arg))
                 }
             }
@@ -70,5 +172,29 @@ pub fn parse(args: &[String]) -> Result<Clargs, ClargsErr> {
         return Err(ClargsErr::new(NoOutput, ""));
     }
 
+    if clargs.cycles.len() == 1 {
+        return Err(ClargsErr::new(NoEndLabel, clargs.cycles[0]));
+    }
+
+    if clargs.range.len() == 1 {
+        return Err(ClargsErr::new(NoEndAddress, ""));
+    }
+
+    if !matches!(clargs.format, Format::Hex|Format::Bin) && !clargs.range.is_empty() {
+        return Err(ClargsErr::new(RangeOfRom, ""));
+    }
+
+    if clargs.format.patch() && clargs.base.is_none() {
+        return Err(ClargsErr::new(NoBase, ""));
+    }
+
     Ok(clargs)
 }
+
+/// Address in decimal, or in hexadecimal after `&` as in the source or `0x`.
+fn address(arg: &str) -> Option<usize> {
+    match arg.strip_prefix('&').or_else(|| arg.strip_prefix("0x")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    program::{ self, clargs::Clargs },
+    parse::{ files::Files, provider::Provider },
+    token::Value,
+    write::constants::{ Constants, ConstExpr },
+    error::{ stage::Report, diagnostic::Diagnostic },
+};
+
+use std::rc::Rc;
+
+/// Output of a successful build.
+#[derive(Debug, Clone)]
+pub struct Assembly {
+    pub rom: Vec<u8>,
+    /// Labels and numeric constants with their value, in the order they are defined.
+    pub symbols: Vec<(String, usize)>,
+    /// Warnings of the build.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Assembly {
+
+    pub fn new(rom: Vec<u8>, constants: &Constants) -> Self {
+        let symbols = constants.const_exprs.iter().filter_map(|(key, value)| match value {
+            ConstExpr::Value(Value::U8(v)) => Some((key.to_string(), *v as usize)),
+            ConstExpr::Value(Value::U16(v)) => Some((key.to_string(), *v as usize)),
+            _ => None,
+        });
+
+        Self{ rom, symbols: symbols.collect(), diagnostics: vec![] }
+    }
+
+    /// Value of a label or a constant.
+    pub fn symbol(&self, name: &str) -> Option<usize> {
+        self.symbols.iter().find(|(key, _)| key == name).map(|(_, value)| *value)
+    }
+
+}
+
+/// Assembles `content` as the main file at `path`, without writing to the disk.
+/// Imported and included files are read from `provider`, by their path relative to the file that uses them.
+/// It can be `Disk`, `Memory`, or a function that returns `None` for files that do not exist.
+/// The standard library is available as in a normal build.
+/// `symbols` are defined for conditional compilation, like `-D`.
+///
+/// Returns every error and warning when the build fails.
+pub fn assemble(
+    path: &str,
+    content: &str,
+    symbols: &[&str],
+    provider: impl Provider + 'static,
+) -> Result<Assembly, Vec<Diagnostic>> {
+    let files = Files::new(provider);
+    files.set_overlay(path, content.into());
+
+    let clargs = Clargs{ path, symbols: symbols.to_vec(), ..Default::default() };
+
+    match program::compile(&clargs, Rc::new(files), Report::silent(), None) {
+        (Some(assembly), diagnostics) => Ok(Assembly{ diagnostics, ..assembly }),
+        (None, diagnostics) => Err(diagnostics),
+    }
+}
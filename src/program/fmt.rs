@@ -10,7 +10,7 @@ pub fn title(title: &str) {
 
 /// Prepends a newline if text is not empty.
 pub fn ln_if(text: &str) -> String {
-    return if text.is_empty() { String::new() }else{ format!("\n{text}") };
+    if text.is_empty() { String::new() }else{ format!("\n{text}") }
 }
 
 #[cfg(target_family="unix")]
@@ -2,74 +2,306 @@
 /// Parses command line arguments.
 pub mod clargs;
 
+/// Rebuilds when the source files change.
+pub mod watch;
+
+/// Formats source files in a standard style.
+pub mod style;
+
 /// Formats text output in the terminal.
 #[allow(dead_code)]
 pub mod fmt;
 
+/// Assembles a program in memory, for tools that use the assembler as a library.
+pub mod embed;
+
 use crate::{
-    error::stage, 
-    parse::{ source::Source, split::Split, prepare },
-    token::{ ast::{ macros::Macros, Ast }, read::TokenRef },
-    write::{ ops::OpMap, constants::Constants, encode },
+    program::{ clargs::Clargs, embed::Assembly },
+    error::{ stage::{ Report, Failure }, diagnostic::{ Diagnostic, Diagnose }, init::{ ClargsErr, ClargsErrType, ReadErr }, asm::InternalErr },
+    parse::{ source::Source, split::Split, prepare::{ self, ParsedToken }, files::Files, lex::TokenType::* },
+    token::{ ast::{ macros::Macros, Ast }, read::TokenRef, lint },
+    write::{ ops::OpMap, constants::Constants, encode, patch, deps, cycles, listing, checks },
 };
 
-use std::time;
+use std::{ time, rc::Rc, collections::HashSet, panic::{ self, AssertUnwindSafe } };
 
+/// Runs the command line program, with `std::env::args` if there are no `args`.
+/// The errors are written before it returns.
+#[allow(clippy::result_unit_err)]
 pub fn run(args: Option<Vec<String>>) -> Result<(), ()> {
-    let start = time::Instant::now();
-
     // Command line arguments.
     let args = args.or_else(|| Some(std::env::args().collect::<Vec<_>>())).unwrap();
-    let clargs = clargs::parse(&args).map_err(stage::clargs)?;
 
+    if args.get(1).is_some_and(|arg| arg == "--lsp") {
+        return crate::lsp::run(&args[2..]);
+    }
+
+    if args.get(1).is_some_and(|arg| arg == "--fmt") {
+        return style::run(&args[2..]);
+    }
+
+    // Errors of the command line are written as JSON before the arguments are parsed.
+    let report = Report::new(args.iter().any(|arg| arg == "--json"));
+    let clargs = match clargs::parse(&args) {
+        Ok(clargs) => clargs,
+
+        Err(e) => {
+            report.clargs(e);
+            report.finish();
+            return Err(());
+        }
+    };
+
+    if clargs.watch {
+        watch::run(&clargs);
+    }
+
+    build(&clargs, Rc::default())
+}
+
+/// Assembles the source, reading files through `files`.
+/// The errors of all the stages are written together.
+pub fn build(clargs: &Clargs, files: Rc<Files>) -> Result<(), ()> {
+    let start = time::Instant::now();
+
+    let output = clargs.output();
+    let (assembly, _) = compile(clargs, files, Report::new(clargs.json), Some(&output));
+
+    if assembly.is_none() {
+        return Err(());
+    }
+
+    // Print success.
+    let ms = start.elapsed().as_millis();
+    let success = fmt::strip()
+        .bold(&format!("\n({ms}ms) "))
+        .ok("Compilation successfull!")
+        .bold(" ---> ")
+        .info(&format!("{output}\n"))
+        .read();
+
+    println!("{success}");
+
+    Ok(())
+}
+
+/// Runs every stage of the build and returns the diagnostics of the report.
+/// The ROM, the makefile rule and the listing are only written to the disk when there is an `output`.
+pub fn compile(
+    clargs: &Clargs,
+    files: Rc<Files>,
+    report: Report,
+    output: Option<&str>,
+) -> (Option<Assembly>, Vec<Diagnostic>) {
     // Get source file.
-    let source = Source::new(clargs.path).map_err(stage::source)?;
+    let source = match Source::new(clargs.path, &clargs.search_paths, files) {
+        Ok(source) => source,
+
+        Err(e) => {
+            report.source(ReadErr::new(clargs.path, e));
+            return (None, report.finish());
+        }
+    };
+
+    let report = report.with_source(&source);
+
+    // A panic is a bug, it is reported without stopping a program that uses the library.
+    let parsed_tokens = panic::catch_unwind(AssertUnwindSafe(|| {
+        // Split source files into words, the lines with errors are left out.
+        let (splits, errors) = Split::all(&source, &clargs.symbols);
+        #[cfg(debug_assertions)] if !report.is_silent() { splits.iter().for_each(Split::debug) }
+
+        if !errors.is_empty() {
+            report.split(errors);
+        }
+
+        // Extract type information and data, then link imported files.
+        let (parsed_tokens, errors) = prepare::parse_all(&source, &splits);
+
+        if !errors.is_empty() {
+            report.parse(errors);
+        }
+
+        parsed_tokens
+    }));
+
+    let parsed_tokens = match parsed_tokens {
+        Ok(parsed_tokens) => parsed_tokens,
+
+        Err(payload) => {
+            report.internal(InternalErr::from_panic(&*payload));
+            return (None, report.finish());
+        }
+    };
+
+    // Build again without the lines of the errors, to find the errors of the other lines.
+    let mut skipped = HashSet::new();
 
-    // Split source files into words.
-    let split = Split::new(source.main(), &clargs.symbols).map_err(stage::split)?;
-    #[cfg(debug_assertions)] split.debug();
+    let assembly = loop {
+        let tokens = parsed_tokens.iter()
+            .filter(|token| !skipped.contains(&(token.file.to_string(), token.line_number)))
+            .copied()
+            .collect::<Vec<_>>();
 
-    // Extract type information and data.
-    let parsed_tokens = prepare::parse(&split).map_err(stage::parse)?;
+        // Every line has an error.
+        if tokens.is_empty() && !skipped.is_empty() {
+            break None;
+        }
 
+        let result = panic::catch_unwind(AssertUnwindSafe(|| assemble(clargs, &source, &report, tokens, output)));
+
+        match result {
+            Ok(Ok(assembly)) => break Some(assembly),
+
+            Err(payload) => {
+                report.internal(InternalErr::from_panic(&*payload));
+                break None;
+            }
+
+            Ok(Err(Failure{ lines })) => {
+                definitions(&parsed_tokens, &lines).into_iter().for_each(|name| report.poison(name));
+
+                let count = skipped.len();
+                skipped.extend(lines);
+
+                if skipped.len() == count {
+                    break None;
+                }
+            }
+        }
+    };
+
+    let assembly = assembly.filter(|_| !report.failed());
+    (assembly, report.finish())
+}
+
+/// Names of the labels and constants defined on `lines`.
+fn definitions<'a>(parsed_tokens: &[ParsedToken<'a>], lines: &[(String, usize)]) -> Vec<&'a str> {
+    let tokens = parsed_tokens.iter()
+        .filter(|token| lines.iter().any(|(file, line)| file == token.file && *line == token.line_number))
+        .collect::<Vec<_>>();
+
+    tokens.iter().enumerate().filter_map(|(i, token)| match token.ty {
+        Label|NamedMark => token.value.as_str().ok(),
+
+        DefB|DefW|Rle|Lz => tokens.get(i + 1)
+            .filter(|ident| ident.ty == Identifier && ident.line_number == token.line_number)
+            .and_then(|ident| ident.value.as_str().ok()),
+
+        _ => None,
+    }).collect()
+}
+
+/// Runs the stages that work on the token tree.
+/// The stages after the constants only run once every earlier stage succeeded.
+fn assemble<'a>(
+    clargs: &Clargs,
+    source: &'a Source,
+    report: &Report<'a>,
+    parsed_tokens: Vec<ParsedToken<'a>>,
+    output: Option<&str>,
+) -> Result<Assembly, Failure> {
     // Build the token tree.
     let mut macros = Macros::new();
-    let mut ast = Ast::new(parsed_tokens, &mut macros, &source).map_err(stage::ast)?;
+    let mut ast = Ast::new(parsed_tokens, &mut macros, source).map_err(|e| report.ast(e))?;
     //#[cfg(debug_assertions)] ast.debug();
-    macros.expand(&mut ast).map_err(stage::macros)?;
-    #[cfg(debug_assertions)] ast.debug();
+    macros.expand(&mut ast).map_err(|e| report.macros(e))?;
+    #[cfg(debug_assertions)] if !report.is_silent() { ast.debug() }
 
     // The token tree is now read-only and easier to traverse.
-    let ast_ref = TokenRef::new(&ast);
+    let ast_ref = TokenRef::new(&ast).map_err(|e| report.ast(vec![e]))?;
 
     // Validate the `Ast`.
-    ast_ref.validate().map_err(stage::ast_validation)?;
+    ast_ref.validate().map_err(|e| report.ast_validation(e))?;
 
     // Identify intructions.
-    let op_map = OpMap::new(&ast_ref).map_err(stage::ops)?;
+    let op_map = OpMap::new(&ast_ref).map_err(|e| report.ops(e))?;
+    cycles::check(&ast_ref, &op_map).map_err(|e| report.cycles(e))?;
 
     // Find and calculate all constants.
-    let mut constants = Constants::new(&ast_ref, &op_map).map_err(stage::constants)?;
-    let updates = constants.eval().map_err(stage::expressions)?;
+    let mut constants = Constants::new(&ast_ref, &op_map).map_err(|e| report.constants(e))?;
+    let updates = constants.eval().map_err(|e| report.expressions(e))?;
     constants.update(updates);
-    #[cfg(debug_assertions)] constants.debug();
+    #[cfg(debug_assertions)] if !report.is_silent() { constants.debug() }
 
-    constants.validate(&ast_ref).map_err(stage::constants_validation)?;
+    constants.validate(&ast_ref).map_err(|e| report.constants_validation(e))?;
 
-    // Write output.
-    encode::build(&clargs.output(), &ast_ref, &op_map, &constants).map_err(stage::encode)?;
+    // Errors of the split and the parser.
+    if report.failed() {
+        return Err(Failure::default());
+    }
 
-    // Print success.
-    #[cfg(not(debug_assertions))] constants.display();
-    let ms = start.elapsed().as_millis();
-    let success = fmt::strip()
-        .bold(&format!("\n({ms}ms) "))
-        .ok("Compilation successfull!")
-        .bold(" ---> ")
-        .info(&format!("{}\n", clargs.output()))
-        .read();
+    // Assertions and messages of the source.
+    let checks = checks::check(&ast_ref, &constants);
 
-    println!("{success}");
+    for (directive, text) in checks.prints.iter().filter(|_| !report.is_silent()) {
+        let print = fmt::strip()
+            .faint(&format!("{}:{}: ", directive.file(), directive.line_number()))
+            .base(text)
+            .read();
 
-    Ok(())
+        println!("{print}");
+    }
+
+    report.warnings(&checks.warnings.iter().map(|w| w as &dyn Diagnose).collect::<Vec<_>>());
+
+    if !checks.errors.is_empty() {
+        return Err(report.checks(checks.errors));
+    }
+
+    // Look for likely mistakes.
+    let (denied, warnings) = clargs.lints.partition(lint::check(&ast_ref, &macros, &clargs.lints));
+    report.warnings(&warnings.iter().map(|w| w as &dyn Diagnose).collect::<Vec<_>>());
+
+    if !denied.is_empty() {
+        return Err(report.lints(denied));
+    }
+
+    // Write output, over the base ROM if there is one.
+    let base = clargs.base
+        .map(|path| source.files.read(path).map_err(|e| report.source(ReadErr::new(path, e))))
+        .transpose()?;
+    let cartridge = clargs.format.cartridge(clargs.range());
+
+    let (bytes, blocks) = encode::build(&ast_ref, &op_map, &constants, cartridge, base.as_deref())
+        .map_err(|e| report.encode(e))?;
+
+    if let Some(base) = &base {
+        let unchanged = patch::unchanged(base, &bytes, &blocks);
+        report.warnings(&unchanged.iter().map(|w| w as &dyn Diagnose).collect::<Vec<_>>());
+    }
+
+    if let Some(output) = output {
+        let content = encode::output(&bytes, clargs.format, clargs.range(), base.as_deref())
+            .map_err(|e| report.encode(e))?;
+
+        encode::write(&content, output).map_err(|e| report.encode(e))?;
+
+        if let Some(path) = clargs.deps {
+            deps::build(path, output, source, &constants, clargs.base).map_err(|e| report.encode(e))?;
+        }
+
+        if let Some(path) = clargs.listing {
+            listing::build(path, &ast_ref, &op_map, &constants, &bytes).map_err(|e| report.encode(e))?;
+        }
+    }
+
+    if let [start, end] = clargs.cycles[..] {
+        let cycles = cycles::between(&ast_ref, &op_map, start, end)
+            .map_err(|label| report.clargs(ClargsErr::new(ClargsErrType::LabelNotFound, label)))?;
+
+        let total = fmt::strip()
+            .base(&format!("\nCycles from {start} to {end}: "))
+            .bold(&cycles.to_string())
+            .read();
+
+        println!("{total}");
+    }
+
+    #[cfg(not(debug_assertions))]
+    if !report.is_silent() {
+        constants.display();
+    }
+
+    Ok(Assembly::new(bytes, &constants))
 }
@@ -0,0 +1,242 @@
+use crate::{
+    program::fmt,
+    parse::lex,
+};
+
+use std::fs;
+
+/// Indentation of instructions, data and macro bodies.
+const INDENT: &str = "    ";
+
+/// Trailing comments start on a multiple of this column.
+const COMMENT_STEP: usize = 4;
+
+/// Formats source files in place, or only checks them.
+///
+/// List of arguments:
+/// --check                 Fail if a file is not formatted, without writing it.
+/// \[FILES\]               Files to format.
+pub fn run(args: &[String]) -> Result<(), ()> {
+    let check = args.iter().any(|arg| arg == "--check");
+    let paths = args.iter().filter(|arg| *arg != "--check").collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        eprintln!("{}", fmt::strip().err("No files to format.").read());
+        return Err(());
+    }
+
+    let mut result = Ok(());
+
+    for path in paths {
+        let input = match fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", fmt::strip().err(&format!("Cannot read '{path}': {e}")).read());
+                result = Err(());
+                continue;
+            }
+        };
+
+        let output = format(&input);
+
+        if output == input {
+            continue;
+        }
+
+        if check {
+            println!("{}", fmt::strip().info(&format!("'{path}' is not formatted.")).read());
+            result = Err(());
+        }
+
+        else if let Err(e) = fs::write(path, output) {
+            eprintln!("{}", fmt::strip().err(&format!("Cannot write '{path}': {e}")).read());
+            result = Err(());
+        }
+    }
+
+    result
+}
+
+/// Formatted line, before comments are aligned.
+struct Line<'a> {
+    /// Indented code, or the whole line when it must be kept as is.
+    code: String,
+    comment: Option<&'a str>,
+    /// Comments are not aligned past this line.
+    breaks_block: bool,
+}
+
+/// Formats a source file:
+/// - Labels and markers are at column 0, as well as directives that do not write data.
+/// - Instructions, data and macro bodies are indented.
+/// - Words of instructions and data are separated by a single space.
+/// - Trailing comments of a block of lines are aligned into one column.
+/// - Lines with only a comment and `#if` blocks are kept as they are.
+pub fn format(input: &str) -> String {
+    let mut lines = vec![];
+    // Depth of nested `#if` blocks.
+    let mut conditions = 0usize;
+    let mut in_macro = false;
+
+    for raw in input.lines() {
+        let raw = raw.trim_end();
+        let (code, comment) = split_comment(raw);
+        let first = code.split_whitespace().next().unwrap_or("");
+
+        if conditions > 0 || first == "#if" {
+            match first {
+                "#if" => conditions += 1,
+                "#endif" => conditions -= 1,
+                _ => (),
+            }
+
+            lines.push(Line{ code: raw.to_string(), comment: None, breaks_block: true });
+            continue;
+        }
+
+        // Blank lines, and lines with only a comment.
+        if first.is_empty() {
+            lines.push(Line{ code: raw.to_string(), comment: None, breaks_block: raw.is_empty() });
+            continue;
+        }
+
+        let code = if first.starts_with(':') || (first.starts_with('&') && first.ends_with(':')) {
+            let rest = normalize(&code.trim_start()[first.len()..]);
+            if rest.is_empty() { first.to_string() }else{ format!("{first} {rest}") }
+        }
+
+        else if first.starts_with('#') {
+            let directive = code.trim();
+
+            match first {
+                "#macro" => {
+                    in_macro = !in_macro;
+                    directive.to_string()
+                }
+
+                // Those write data where they are.
+                "#include"|"#rle"|"#lz" => format!("{INDENT}{directive}"),
+
+                _ if in_macro => format!("{INDENT}{directive}"),
+                _ => directive.to_string(),
+            }
+        }
+
+        else {
+            format!("{INDENT}{}", normalize(code))
+        };
+
+        lines.push(Line{ code, comment, breaks_block: false });
+    }
+
+    align(&lines)
+}
+
+/// Splits the trailing comment from the code, ignoring `;` in string literals.
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut str_literal = false;
+
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => str_literal = !str_literal,
+            ';' if !str_literal => return (&line[..i], Some(&line[i..])),
+            _ => (),
+        }
+    }
+
+    (line, None)
+}
+
+/// Separates words with a single space.
+/// There are no spaces inside parentheses.
+fn normalize(code: &str) -> String {
+    let mut words: Vec<&str> = vec![];
+    let mut str_literal = false;
+    let mut word_start = None;
+
+    for (i, ch) in code.char_indices() {
+        if str_literal {
+            if ch == '"' {
+                words.push(&code[word_start.take().unwrap()..=i]);
+                str_literal = false;
+            }
+
+            continue;
+        }
+
+        if ch == '"' || ch.is_whitespace() || lex::is_char_word(ch) {
+            if let Some(start) = word_start.take() {
+                words.push(&code[start..i]);
+            }
+
+            if ch == '"' {
+                word_start = Some(i);
+                str_literal = true;
+            }
+
+            else if !ch.is_whitespace() {
+                words.push(&code[i..i + ch.len_utf8()]);
+            }
+        }
+
+        else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+
+    // Unterminated string literals are kept until the end of the line.
+    if let Some(start) = word_start {
+        words.push(code[start..].trim_end());
+    }
+
+    let mut output = String::new();
+    let mut previous = "";
+
+    for word in words {
+        if !(previous.is_empty() || previous == "(" || word == ")") {
+            output.push(' ');
+        }
+
+        output.push_str(word);
+        previous = word;
+    }
+
+    output
+}
+
+/// Aligns the trailing comments of each block of lines.
+/// Blocks are separated by empty lines and `#if` blocks.
+fn align(lines: &[Line]) -> String {
+    let mut output = String::new();
+
+    for block in lines.split_inclusive(|line| line.breaks_block) {
+        let column = block.iter()
+            .filter(|line| line.comment.is_some())
+            .map(|line| line.code.chars().count() + 2)
+            .max()
+            .unwrap_or(0)
+            .next_multiple_of(COMMENT_STEP);
+
+        for line in block {
+            output.push_str(&line.code);
+
+            if let Some(comment) = line.comment {
+                let width = line.code.chars().count();
+                output.push_str(&" ".repeat(column - width));
+                output.push_str(comment);
+            }
+
+            output.push('\n');
+        }
+    }
+
+    // A single newline at the end of the file.
+    let len = output.trim_end().len();
+    output.truncate(len);
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,29 @@
+use crate::{
+    program::{ build, clargs::Clargs, fmt },
+    parse::files::Files,
+};
+
+use std::{ rc::Rc, thread, time::Duration };
+
+/// Time between two checks for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Builds, then rebuilds every time a file used or looked for by the last build changes.
+/// Files are kept between builds, only the modified ones are read and split again.
+pub fn run(clargs: &Clargs) -> ! {
+    let files = Rc::new(Files::default());
+
+    loop {
+        let _ = build(clargs, Rc::clone(&files));
+
+        let msg = fmt::strip()
+            .faint(&format!("Watching {} files for changes...\n", files.count()))
+            .read();
+
+        println!("{msg}");
+
+        while !files.changed() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    program::embed::assemble,
+    parse::{ provider::Memory, split::Split, prepare, source::Source, lex::TokenType::{ self, * } },
+    token::{ ast::{ macros::Macros, Ast }, read::TokenRef },
+    error::diagnostic::Severity,
+};
+
+const INPUT: &str = "
+#assert SIZE LE 256 \"Too big\"
+#assert SIZE
+#print \"Size:\" SIZE
+#warn \"Always\"
+#if DEBUG
+#error \"Skipped\"
+#endif
+";
+
+#[test]
+fn message() {
+    let source = Source::from_content(INPUT.into());
+    let split = Split::new(source.main(), &[]).unwrap();
+    let parsed_tokens = prepare::parse(&split).unwrap();
+    let mut macros = Macros::new();
+    let ast = Ast::new(parsed_tokens, &mut macros, &source).unwrap();
+    let ast_ref = TokenRef::new(&ast).unwrap();
+
+    let children = |directive: &TokenRef| -> Vec<TokenType> {
+        directive.first().children().iter().map(|child| child.ty()).collect()
+    };
+
+    // The message is taken out of the expression, which is optional for messages.
+    let directives = ast_ref.children();
+    assert_eq!(directives.len(), 4);
+    assert_eq!(children(directives[0]), vec![Expr, Lit]);
+    assert_eq!(children(directives[1]), vec![Expr]);
+    assert_eq!(children(directives[2]), vec![Expr, Lit]);
+    assert_eq!(children(directives[3]), vec![Lit]);
+}
+
+/// A failing assertion fails the build with its message, at its line.
+#[test]
+fn assert() {
+    let main = "#db SIZE 0\n#assert SIZE \"Too big\"\n&0150:\n";
+    let diagnostics = assemble("main.gb.asm", main, &[], Memory::new()).unwrap_err();
+    let errors = diagnostics.iter().map(|d| (d.code.as_str(), d.message.as_str(), d.line)).collect::<Vec<_>>();
+
+    assert_eq!(errors, [("AssertFailed", "Too big", 2)]);
+}
+
+/// Messages from a macro body are reported at the call, and warnings are kept when the checks fail.
+#[test]
+fn macro_messages() {
+    let main = "#macro check. .n\n#warn .n \"Warned\"\n#error .n \"Failed\"\n#macro\n&0150:\n    check. 1\n";
+    let diagnostics = assemble("main.gb.asm", main, &[], Memory::new()).unwrap_err();
+    let reports = diagnostics.iter().map(|d| (d.severity, d.message.as_str(), d.line)).collect::<Vec<_>>();
+
+    assert_eq!(reports, [(Severity::Error, "Failed", 6), (Severity::Warning, "Warned", 6)]);
+
+    let assembly = assemble("main.gb.asm", "#warn \"Always\"\n&0150:\n", &[], Memory::new()).unwrap();
+    let warnings = assembly.diagnostics.iter().map(|d| (d.severity, d.message.as_str(), d.line)).collect::<Vec<_>>();
+
+    assert_eq!(warnings, [(Severity::Warning, "Always", 1)]);
+}
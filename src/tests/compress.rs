@@ -0,0 +1,103 @@
+use crate::{
+    program::embed::assemble,
+    parse::provider::Memory,
+    write::compress,
+};
+
+use super::urand;
+
+/// Reference decoder for the RLE format.
+fn unrle(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut i = 0;
+
+    loop {
+        let n = data[i] as usize;
+        i += 1;
+
+        match n {
+            0 => return out,
+            0x01..=0x7F => { out.extend_from_slice(&data[i..i + n]); i += n; }
+            _ => { out.extend(std::iter::repeat_n(data[i], (n & 0x7F) + 2)); i += 1; }
+        }
+    }
+}
+
+/// Reference decoder for the LZ format.
+fn unlz(data: &[u8]) -> Vec<u8> {
+    let mut out: Vec<u8> = vec![];
+    let mut i = 0;
+
+    loop {
+        let n = data[i] as usize;
+        i += 1;
+
+        match n {
+            0 => return out,
+            0x01..=0x7F => { out.extend_from_slice(&data[i..i + n]); i += n; }
+
+            _ => {
+                let distance = u16::from_le_bytes([data[i], data[i + 1]]) as usize;
+                let start = out.len() - distance;
+                i += 2;
+
+                for j in 0..(n & 0x7F) + 3 {
+                    out.push(out[start + j]);
+                }
+            }
+        }
+    }
+}
+
+/// Random data with runs and repeated sequences.
+fn rand_data() -> Vec<u8> {
+    let len = urand(3000);
+    let mut data: Vec<u8> = vec![];
+
+    while data.len() < len {
+        match urand(3) {
+            0 => data.push(urand(256) as u8),
+            1 => data.extend(std::iter::repeat_n(urand(4) as u8, urand(300))),
+
+            _ if !data.is_empty() => {
+                let start = urand(data.len());
+                let end = (start + urand(200)).min(data.len());
+                data.extend_from_within(start..end);
+            }
+
+            _ => {}
+        }
+    }
+
+    data
+}
+
+#[test]
+fn rle() {
+    assert_eq!(compress::rle(&[]), [0]);
+
+    for _ in 0..200 {
+        let data = rand_data();
+        assert_eq!(unrle(&compress::rle(&data)), data);
+    }
+}
+
+#[test]
+fn lz() {
+    assert_eq!(compress::lz(&[]), [0]);
+
+    for _ in 0..200 {
+        let data = rand_data();
+        assert_eq!(unlz(&compress::lz(&data)), data);
+    }
+}
+
+#[test]
+fn bare_directive() {
+    for directive in ["#rle", "#lz"] {
+        let diagnostics = assemble("main.gb.asm", &format!("{directive}\n&0150:\n"), &[], Memory::new()).unwrap_err();
+        let codes = diagnostics.iter().map(|d| (d.code.as_str(), d.line)).collect::<Vec<_>>();
+
+        assert_eq!(codes, [("NoFilePath", 1)], "{directive}");
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    parse::{ split::Split, prepare, source::Source, lex::TokenType },
+    token::{ ast::{ macros::Macros, Ast }, read::TokenRef },
+    write::{ ops::OpMap, instructions, cycles::{ self, Cycles } },
+};
+
+const INPUT: &str = "
+:Start
+#cycles 8
+    ld a 1
+    ld b 2
+    jp NZ Start
+:Loop
+#cycles 4
+    ld hl &C000
+    ld (hl) a
+:End
+";
+
+#[test]
+fn table() {
+    for i in 0..TokenType::COUNT {
+        let Some((_, ops)) = instructions::get_instruction_info(TokenType::at(i)) else { continue };
+
+        for (_, code, (taken, skipped), _) in ops {
+            assert!(skipped > 0 && taken >= skipped, "{code:02X}");
+        }
+    }
+}
+
+#[test]
+fn budget() {
+    let source = Source::from_content(INPUT.into());
+    let split = Split::new(source.main(), &[]).unwrap();
+    let parsed_tokens = prepare::parse(&split).unwrap();
+    let mut macros = Macros::new();
+    let mut ast = Ast::new(parsed_tokens, &mut macros, &source).unwrap();
+    macros.expand(&mut ast).unwrap();
+    let ast_ref = TokenRef::new(&ast).unwrap();
+    let op_map = OpMap::new(&ast_ref).unwrap();
+
+    // Only the second block is over its budget.
+    let errors = cycles::check(&ast_ref, &op_map).unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    assert_eq!(cycles::between(&ast_ref, &op_map, "Start", "Loop"), Ok(Cycles{ taken: 8, skipped: 7 }));
+    assert_eq!(cycles::between(&ast_ref, &op_map, "Start", "End"), Ok(Cycles{ taken: 13, skipped: 12 }));
+    assert_eq!(cycles::between(&ast_ref, &op_map, "Loop", "Start"), Err("Start"));
+    assert_eq!(cycles::between(&ast_ref, &op_map, "Nowhere", "End"), Err("Nowhere"));
+}
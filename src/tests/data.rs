@@ -0,0 +1,28 @@
+use crate::{
+    program::embed::assemble,
+    parse::provider::Memory,
+};
+
+#[test]
+fn values() {
+    let main = "#db LIVES 3\n&0150:\n:Table\n#word Table Next (LIVES)\n:Next\n#byte \"AB\" (LIVES) 7\n#fill 3 &FF\n";
+    let assembly = assemble("main.gb.asm", main, &[], Memory::new()).unwrap();
+
+    assert_eq!(assembly.symbol("Next"), Some(0x0156));
+    assert_eq!(&assembly.rom[0x0150..], &[0x50, 0x01, 0x56, 0x01, 0x03, 0x00, b'A', b'B', 3, 7, 0xFF, 0xFF, 0xFF]);
+
+    let overflow = assemble("main.gb.asm", "&0150:\n#byte 1 (256)\n", &[], Memory::new()).unwrap_err();
+    assert_eq!((overflow[0].code.as_str(), overflow[0].line), ("DataOverflow", 2));
+}
+
+#[test]
+fn alignment() {
+    let main = "&0150:\n    ret\n#align 4 0\n:Table\n#aligned 4\n#byte 1\n#align 2\n#byte 2\n";
+    let assembly = assemble("main.gb.asm", main, &[], Memory::new()).unwrap();
+
+    assert_eq!(assembly.symbol("Table"), Some(0x0160));
+    assert_eq!(&assembly.rom[0x015E..], &[0, 0, 1, 0xFF, 0xFF, 0xFF, 2]);
+
+    let misaligned = assemble("main.gb.asm", "&0150:\n#aligned 8\n", &[], Memory::new()).unwrap_err();
+    assert_eq!((misaligned[0].code.as_str(), misaligned[0].line), ("Misaligned", 2));
+}
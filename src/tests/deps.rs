@@ -0,0 +1,40 @@
+use crate::{
+    program::{ self, clargs::Clargs },
+    parse::{ files::Files, provider::Memory },
+    error::stage::Report,
+};
+
+use std::rc::Rc;
+
+/// Lists the imports and the includes, with the characters make reads escaped.
+#[test]
+fn make_rule() {
+    let files = Memory::new()
+        .with("my game/main.gb.asm", "#import \"lib$1.gb.asm\"\n&0150:\n    #include \"#data.bin\"\n")
+        .with("my game/lib$1.gb.asm", "nop\n")
+        .with("my game/#data.bin", [1, 2, 3]);
+
+    let dir = std::env::temp_dir();
+    let (output, deps) = (dir.join("gbz80 deps.gb"), dir.join("gbz80 deps.d"));
+    let (output, deps) = (output.to_str().unwrap(), deps.to_str().unwrap());
+
+    let clargs = Clargs{ path: "my game/main.gb.asm", deps: Some(deps), ..Default::default() };
+    let (assembly, diagnostics) = program::compile(&clargs, Rc::new(Files::new(files)), Report::silent(), Some(output));
+
+    assert!(assembly.is_some(), "{diagnostics:?}");
+
+    let rule = std::fs::read_to_string(deps).unwrap();
+    let _ = (std::fs::remove_file(output), std::fs::remove_file(deps));
+    let target = output.replace(' ', "\\ ");
+
+    assert_eq!(rule, format!("\
+{target}: \\
+  my\\ game/main.gb.asm \\
+  my\\ game/lib$$1.gb.asm \\
+  my\\ game/\\#data.bin
+
+my\\ game/lib$$1.gb.asm:
+
+my\\ game/\\#data.bin:
+"));
+}
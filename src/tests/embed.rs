@@ -0,0 +1,81 @@
+use crate::{
+    program::embed::assemble,
+    parse::{ source::Source, files::Files, provider::Memory },
+    error::diagnostic::Severity,
+};
+
+use std::rc::Rc;
+
+fn resolve(path: &str) -> Option<Vec<u8>> {
+    match path {
+        "game/lib.gb.asm" => Some(b"#db VALUE 3\n".to_vec()),
+        "game/data.bin" => Some(vec![1, 2, 3]),
+        _ => None,
+    }
+}
+
+#[test]
+fn memory() {
+    let main = "#import \"lib.gb.asm\"\n&0100: nop\n&0150:\n:Data\n    #include \"data.bin\"\n    ld a VALUE\n";
+    let files = Memory::new()
+        .with("game/lib.gb.asm", "#db VALUE 3\n")
+        .with("game/data.bin", [1, 2, 3]);
+
+    let assembly = assemble("game/main.gb.asm", main, &[], files).unwrap();
+
+    assert_eq!(assembly.symbol("Data"), Some(0x0150));
+    assert_eq!(assembly.symbol("VALUE"), Some(3));
+    assert_eq!(&assembly.rom[0x0150..], &[1, 2, 3, 0x3E, 3]);
+}
+
+/// Imports are found next to the file, then in the search paths.
+#[test]
+fn search_paths() {
+    let files = Memory::new()
+        .with("game/main.gb.asm", "#import \"a.gb.asm\"\n#import \"b.gb.asm\"\n")
+        .with("game/a.gb.asm", "nop\n")
+        .with("lib/b.gb.asm", "nop\n");
+
+    let source = Source::new("game/main.gb.asm", &["lib"], Rc::new(Files::new(files))).unwrap();
+    let paths = source.inputs.iter().map(|input| input.path.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(paths, ["game/main.gb.asm", "game/a.gb.asm", "lib/b.gb.asm"]);
+}
+
+#[test]
+fn missing() {
+    let main = "&0150:\n    #include \"other.bin\"\n    ld a UNKNOWN\n";
+    let diagnostics = assemble("game/main.gb.asm", main, &[], resolve).unwrap_err();
+
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error && d.file == "game/main.gb.asm"));
+}
+
+/// A bug of the assembler is returned as a diagnostic of its line, and does not stop the process.
+#[test]
+fn internal() {
+    let main = format!("#db A {}1{}\n", "(".repeat(1100), ")".repeat(1100));
+    let diagnostics = assemble("game/main.gb.asm", &main, &[], resolve).unwrap_err();
+
+    assert!(diagnostics.iter().any(|d| d.code == "Internal" && d.line == 1));
+}
+
+/// Code running past the next marker names the block it starts from.
+#[test]
+fn overlap() {
+    let main = "&0150:\n    ld a 1\n    ld b 2\n&0153:\n    nop\n";
+    let diagnostics = assemble("game/main.gb.asm", main, &[], resolve).unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].code.as_str(), diagnostics[0].line), ("Overlap", 4));
+    assert!(diagnostics[0].message.ends_with("the block from &0150 is 1 byte too long"));
+}
+
+/// A constant whose definition has an error is not reported again where it is used.
+#[test]
+fn poisoned() {
+    let main = "#db VALUE (UNKNOWN)\n#db OTHER (VALUE)\n&0150:\n    ld a VALUE\n    ld a VALUE\n    ld a OTHER\n";
+    let diagnostics = assemble("game/main.gb.asm", main, &[], resolve).unwrap_err();
+
+    assert_eq!(diagnostics.iter().map(|d| (d.code.as_str(), d.line)).collect::<Vec<_>>(), [("ConstantNotFound", 1)]);
+}
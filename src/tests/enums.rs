@@ -0,0 +1,18 @@
+use crate::{
+    program::embed::assemble,
+    parse::provider::Memory,
+};
+
+#[test]
+fn values() {
+    let main = "#db BASE 16\n#enum ZERO ONE\n#enum \"DIR_\" (BASE) 4 UP DOWN LEFT\n&0150:\n    ld a DIR_LEFT\n";
+    let assembly = assemble("main.gb.asm", main, &[], Memory::new()).unwrap();
+    let symbol = |name| assembly.symbol(name).unwrap();
+
+    assert_eq!((symbol("ZERO"), symbol("ONE")), (0, 1));
+    assert_eq!((symbol("DIR_UP"), symbol("DIR_LEFT"), symbol("DIR_COUNT")), (16, 24, 3));
+    assert_eq!(&assembly.rom[0x0150..], &[0x3E, 24]);
+
+    let overflow = assemble("main.gb.asm", "#enum 250 10 LOW HIGH\n", &[], Memory::new()).unwrap_err();
+    assert_eq!((overflow[0].code.as_str(), overflow[0].line), ("EnumOverflow", 1));
+}
@@ -0,0 +1,69 @@
+use crate::{
+    program::{ self, clargs::Clargs },
+    parse::{ files::Files, provider::{ Provider, Memory } },
+    error::stage::Report,
+};
+
+use std::{ cell::{ Cell, RefCell }, io, rc::Rc, time::SystemTime };
+
+/// Files in memory that count their reads, with a modification time to be cached.
+#[derive(Clone, Default)]
+struct Counting {
+    files: Rc<RefCell<Memory>>,
+    reads: Rc<Cell<usize>>,
+}
+
+impl Provider for Counting {
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        self.files.borrow().read(path)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.borrow().exists(path)
+    }
+
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        self.exists(path).then_some(SystemTime::UNIX_EPOCH)
+    }
+
+}
+
+fn build(files: &Rc<Files>) -> bool {
+    let clargs = Clargs{ path: "main.gb.asm", ..Default::default() };
+    program::compile(&clargs, Rc::clone(files), Report::silent(), None).0.is_some()
+}
+
+/// Unchanged files are not read or split again by the next build.
+#[test]
+fn cached() {
+    let provider = Counting::default();
+    provider.files.borrow_mut().insert("main.gb.asm", "#import \"lib.gb.asm\"\n&0150:\n");
+    provider.files.borrow_mut().insert("lib.gb.asm", "nop\n");
+
+    let files = Rc::new(Files::new(provider.clone()));
+
+    assert!(build(&files));
+    assert!(build(&files));
+    assert_eq!(provider.reads.get(), 2);
+    assert!(files.spans("lib.gb.asm").is_some());
+    assert!(!files.changed());
+}
+
+/// Creating an import that was not found is a change.
+#[test]
+fn missing_import() {
+    let provider = Counting::default();
+    provider.files.borrow_mut().insert("main.gb.asm", "#import \"lib.gb.asm\"\n&0150:\n");
+
+    let files = Rc::new(Files::new(provider.clone()));
+
+    assert!(!build(&files));
+    assert!(!files.changed());
+
+    provider.files.borrow_mut().insert("lib.gb.asm", "nop\n");
+
+    assert!(files.changed());
+    assert!(build(&files));
+}
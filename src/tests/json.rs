@@ -0,0 +1,66 @@
+use crate::{
+    lsp::json::Json,
+    program::{ self, clargs::Clargs },
+    parse::{ source::Source, files::Files, provider::Memory, lex::TokenType::Identifier },
+    error::{ ErrCtx, stage::Report, asm::{ ExprMsg, CheckMsg }, diagnostic::Diagnose },
+};
+
+use std::rc::Rc;
+
+#[test]
+fn parse() {
+    let text = r#" { "a": [1, -2.5e1, true, null], "b": { "c": "\"\u00e9\ud83d\ude00\n" } } "#;
+    let json = Json::parse(text).unwrap();
+
+    assert_eq!(json.at(&["a"]).as_array().len(), 4);
+    assert_eq!(json.get("a").as_array()[1], Json::Number(-25.0));
+    assert_eq!(json.at(&["b", "c"]).as_str(), Some("\"é😀\n"));
+    assert!(json.at(&["b", "d"]).is_null());
+
+    for bad in ["", "{", "[1,]", "{\"a\" 1}", "\"\\x\"", "1 2", "tru"] {
+        assert!(Json::parse(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn round_trip() {
+    let json = Json::object([
+        ("text", "tab\t \"quote\" \\ \u{1}".into()),
+        ("list", vec![Json::Null, true.into(), 3.into()].into()),
+        ("empty", Json::object([])),
+    ]);
+
+    let text = json.to_string();
+    assert_eq!(text, r#"{"text":"tab\t \"quote\" \\ \u0001","list":[null,true,3],"empty":{}}"#);
+    assert_eq!(Json::parse(&text).unwrap(), json);
+}
+
+#[test]
+fn diagnostic() {
+    let source = Source::from_content("#db A 1\n#db B A + C\n".into());
+    let input = source.main();
+    let line = input.lines().nth(1).unwrap();
+    let word = &line[10..11];
+
+    let ctx = ErrCtx::new(Identifier, &input.path, 2, line, word);
+    let err = err!(CheckMsg, CheckMsg::Expr(ExprMsg::ConstantNotFound), ctx);
+    let json = err.diagnostic(Some(&source)).to_json("checks");
+
+    assert_eq!(json.get("stage").as_str(), Some("checks"));
+    assert_eq!(json.get("code").as_str(), Some("ConstantNotFound"));
+    assert_eq!(json.get("line").as_usize(), Some(2));
+    assert_eq!(json.get("columns").as_array(), &[Json::from(10), Json::from(11)]);
+    assert_eq!(json.get("word").as_str(), Some("C"));
+}
+
+/// A file that cannot be read is reported with its path.
+#[test]
+fn unreadable() {
+    let clargs = Clargs{ path: "missing.gb.asm", ..Default::default() };
+    let (_, diagnostics) = program::compile(&clargs, Rc::new(Files::new(Memory::new())), Report::silent(), None);
+    let json = diagnostics[0].to_json("source");
+
+    assert_eq!(json.get("code").as_str(), Some("NotFound"));
+    assert_eq!(json.get("file").as_str(), Some("missing.gb.asm"));
+    assert_eq!(json.get("word").as_str(), Some("missing.gb.asm"));
+}
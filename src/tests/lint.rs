@@ -0,0 +1,49 @@
+use crate::{
+    parse::{ split::Split, prepare, source::Source },
+    token::{ ast::{ macros::Macros, Ast }, read::TokenRef, lint::{ self, Lints, Level } },
+    error::asm::LintMsg::{ self, * },
+};
+
+const INPUT: &str = "
+#db UNUSED 3
+#db USED 4
+#macro never.
+    nop
+#macro
+:Start
+    ld a USED
+    ld b b
+    jp Start
+    nop
+:Loop
+    inc a
+:Data
+    &01 &02
+";
+
+fn found(lints: &Lints) -> Vec<LintMsg> {
+    let source = Source::from_content(INPUT.into());
+    let split = Split::new(source.main(), &[]).unwrap();
+    let parsed_tokens = prepare::parse(&split).unwrap();
+    let mut macros = Macros::new();
+    let mut ast = Ast::new(parsed_tokens, &mut macros, &source).unwrap();
+    macros.expand(&mut ast).unwrap();
+    let ast_ref = TokenRef::new(&ast).unwrap();
+
+    lint::check(&ast_ref, &macros, lints).iter().map(|lint| lint.ty).collect()
+}
+
+#[test]
+fn lints() {
+    let mut lints = Lints::default();
+    let mut all = found(&lints);
+    all.sort_by_key(|lint| lint.name());
+
+    assert_eq!(all, [FallThrough, SelfLoad, Unreachable, UnusedConstant, UnusedLabel, UnusedLabel, UnusedMacro]);
+
+    lints.set("all", Level::Allow).unwrap();
+    lints.set("self-load", Level::Deny).unwrap();
+    assert!(lints.set("bogus", Level::Warn).is_err());
+
+    assert_eq!(found(&lints), [SelfLoad]);
+}
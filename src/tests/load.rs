@@ -0,0 +1,18 @@
+use crate::{
+    program::embed::assemble,
+    parse::provider::Memory,
+};
+
+#[test]
+fn addresses() {
+    let main = "#dw HRAM &FF80\n&0150:\n    call Copied\n#load COPY (HRAM)\n:Copied\n    jp Copied\n#endload\n:After\n    ret\n";
+    let assembly = assemble("main.gb.asm", main, &[], Memory::new()).unwrap();
+    let symbol = |name| assembly.symbol(name).unwrap();
+
+    assert_eq!((symbol("Copied"), symbol("After")), (0xFF80, 0x0156));
+    assert_eq!((symbol("COPY_ROM"), symbol("COPY_RAM"), symbol("COPY_SIZE")), (0x0153, 0xFF80, 3));
+    assert_eq!(&assembly.rom[0x0150..], &[0xCD, 0x80, 0xFF, 0xC3, 0x80, 0xFF, 0xC9]);
+
+    let unclosed = assemble("main.gb.asm", "&0150:\n#load COPY &C000\n    ret\n", &[], Memory::new()).unwrap_err();
+    assert_eq!((unclosed[0].code.as_str(), unclosed[0].line), ("BadLoad", 2));
+}
@@ -0,0 +1,30 @@
+use crate::{
+    lsp::{ word_at, analysis },
+    program::clargs::Clargs,
+    parse::{ files::Files, provider::Memory },
+    token::{ Value, lint::Level },
+};
+
+use std::rc::Rc;
+
+/// Positions count the characters outside of the basic plane twice, as UTF-16 does.
+#[test]
+fn utf16() {
+    let main = "#db LIVES 3\n#byte \"é🎮\" UNKNOWN LIVES\n:Start\n#db UNUSED 1\n";
+    let files = Rc::new(Files::new(Memory::new().with("main.gb.asm", main)));
+
+    let mut settings = Clargs::default();
+    settings.lints.set("unused-constant", Level::Allow).unwrap();
+
+    let analysis = analysis::analyze("main.gb.asm", &settings, Rc::clone(&files));
+    let codes = analysis.diagnostics.iter().map(|d| (d.code.as_str(), d.line, d.columns)).collect::<Vec<_>>();
+
+    assert_eq!(codes, [("IdentNotFound", 2, (12, 19))]);
+    assert_eq!(word_at("#byte \"é🎮\" UNKNOWN LIVES", 20).as_deref(), Some("LIVES"));
+
+    files.set_overlay("main.gb.asm", "#db LIVES 3\n#byte \"é🎮\" LIVES\n".into());
+    let analysis = analysis::analyze("main.gb.asm", &settings, files);
+
+    assert!(analysis.diagnostics.is_empty(), "{:?}", analysis.diagnostics);
+    assert!(matches!(analysis.symbol("LIVES").and_then(|symbol| symbol.value), Some(Value::U8(3))));
+}
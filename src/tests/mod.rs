@@ -2,6 +2,54 @@
 /// Runs tests for various stages of the assembler.
 mod stages;
 
+/// Splits lines with characters of several bytes.
+mod split;
+
+/// Round trips data through the compression formats, and rejects directives without a file.
+mod compress;
+
+/// Parses and writes JSON for the language server and diagnostics.
+mod json;
+
+/// Analyzes documents for the language server.
+mod lsp;
+
+/// Formats source files.
+mod style;
+
+/// Finds likely mistakes in the token tree.
+mod lint;
+
+/// Counts the cycles of instructions.
+mod cycles;
+
+/// Reads the message of assertions and user messages, and reports them when assembling.
+mod checks;
+
+/// Assembles programs of several files in memory.
+mod embed;
+
+/// Writes the output formats.
+mod output;
+
+/// Writes the makefile rule of the files of a build.
+mod deps;
+
+/// Keeps the files of a build for the next one, until they change.
+mod files;
+
+/// Lays out structs and variables in RAM.
+mod structs;
+
+/// Numbers the names of enums.
+mod enums;
+
+/// Writes bytes, words, fills and alignments of data.
+mod data;
+
+/// Resolves the labels of code copied to RAM.
+mod load;
+
 use std::fs::File;
 use std::io::Read;
 
@@ -0,0 +1,103 @@
+use crate::{
+    write::{ encode::{ self, Format }, patch },
+    program::clargs,
+    error::init::ClargsErrType,
+};
+
+#[test]
+fn intel_hex() {
+    let bytes = (0..0x0170).map(|i| i as u8).collect::<Vec<_>>();
+    let hex = encode::output(&bytes, Format::Hex, Some((0x0150, 0x0162)), None).unwrap();
+    let hex = String::from_utf8(hex).unwrap();
+
+    assert_eq!(hex.lines().collect::<Vec<_>>(), [
+        ":10015000505152535455565758595A5B5C5D5E5F27",
+        ":020160006061DC",
+        ":00000001FF",
+    ]);
+}
+
+#[test]
+fn segment() {
+    let bytes = (0..0x0170).map(|i| i as u8).collect::<Vec<_>>();
+
+    assert_eq!(encode::output(&bytes, Format::Bin, Some((0x0150, 0x0153)), None).unwrap(), [0x50, 0x51, 0x52]);
+    assert!(encode::output(&bytes, Format::Bin, Some((0x0160, 0x0180)), None).is_err());
+    assert!(Format::Hex.cartridge(None) && !Format::Bin.cartridge(None) && !Format::Hex.cartridge(Some((0, 1))));
+}
+
+#[test]
+fn range_args() {
+    let args = |extra: &[&str]| ["", "main.gb.asm", "-o", "out"].iter().chain(extra).map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let ok = args(&["-f", "bin", "-r", "&C000", "49408"]);
+    assert_eq!(clargs::parse(&ok).unwrap().range(), Some((0xC000, 0xC100)));
+
+    let rom = args(&["-r", "0x100", "0x200"]);
+    assert!(matches!(clargs::parse(&rom).map_err(|e| e.ty()), Err(ClargsErrType::RangeOfRom)));
+}
+
+/// Applies the patches as an emulator would.
+#[test]
+fn patches() {
+    let base = (0..0x0200).map(|i| i as u8).collect::<Vec<_>>();
+    let mut target = base.clone();
+    target[0x0150..0x0153].copy_from_slice(&[0xC3, 0x00, 0x02]);
+    target[0x0160] = 0xAF;
+    target.extend_from_slice(&[0xFF; 4]);
+
+    let ips = patch::ips(&base, &target).unwrap();
+    let mut patched = base.clone();
+    let mut at = 5;
+
+    while &ips[at..at + 3] != b"EOF" {
+        let offset = u32::from_be_bytes([0, ips[at], ips[at + 1], ips[at + 2]]) as usize;
+        let len = u16::from_be_bytes([ips[at + 3], ips[at + 4]]) as usize;
+        patched.resize(patched.len().max(offset + len), 0);
+        patched[offset..offset + len].copy_from_slice(&ips[at + 5..at + 5 + len]);
+        at += 5 + len;
+    }
+
+    assert_eq!(patched, target);
+    assert_eq!(&ips[..5], b"PATCH");
+
+    let bps = patch::bps(&base, &target);
+    let mut at = 4;
+    let sizes = [number(&bps, &mut at), number(&bps, &mut at), number(&bps, &mut at)];
+    assert_eq!(sizes, [base.len(), target.len(), 0]);
+
+    let mut patched = vec![];
+
+    while patched.len() < target.len() {
+        let action = number(&bps, &mut at);
+        let len = (action >> 2) + 1;
+
+        if action & 3 == 0 {
+            patched.extend_from_slice(&base[patched.len()..patched.len() + len]);
+        }else{
+            patched.extend_from_slice(&bps[at..at + len]);
+            at += len;
+        }
+    }
+
+    assert_eq!(patched, target);
+    assert_eq!(patch::crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(bps[bps.len() - 4..], patch::crc32(&bps[..bps.len() - 4]).to_le_bytes());
+}
+
+fn number(bps: &[u8], at: &mut usize) -> usize {
+    let (mut n, mut shift) = (0, 1);
+
+    loop {
+        let byte = bps[*at] as usize;
+        *at += 1;
+        n += (byte & 0x7F) * shift;
+
+        if byte & 0x80 != 0 {
+            return n;
+        }
+
+        shift <<= 7;
+        n += shift;
+    }
+}
@@ -0,0 +1,10 @@
+use crate::parse::{ source::Source, split::Split };
+
+#[test]
+fn non_ascii() {
+    let source = Source::from_content("ld a \"é\" ;à côté\n\"ü\" 2 3\n".into());
+    let split = Split::new(source.main(), &[]).unwrap();
+    let words = split.words().into_iter().map(|(word, ..)| word).collect::<Vec<_>>();
+
+    assert_eq!(words, ["ld", "a", "\"é", "\"ü", "2", "3"]);
+}
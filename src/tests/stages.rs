@@ -4,11 +4,13 @@ use crate::{
     token::{ Value, ast::{ macros::Macros, Ast }, read::TokenRef },
     write::{ ops::OpMap, constants::Constants, encode },
     program::clargs,
-    error::stage,
+    error::{ stage::{ Report, Failure }, init::ReadErr },
 };
 
 use super::{ rand_file, rand_word, urand, };
 
+use std::rc::Rc;
+
 #[test]
 #[ignore]
 fn endless() {
@@ -31,6 +33,19 @@ fn split() {
     }
 }
 
+#[test]
+fn split_spans() {
+    let symbols = ["TEST"];
+
+    for _ in 0..100 {
+        let source = Source::from_content(rand_file()); 
+        let Ok(split) = Split::new(source.main(), &symbols) else { continue };
+        let kept = split.spans(source.main()).split(source.main());
+
+        assert_eq!(split.words(), kept.words());
+    }
+}
+
 #[test]
 fn parse1() {
     let symbols = ["TEST"];
@@ -109,7 +124,7 @@ fn shuffle() {
     }
 }
 
-fn _shuffle() -> Result<(), ()> {
+fn _shuffle() -> Result<(), Failure> {
     let args = vec![
         String::new(),
         String::from("asm/hello/hello.gb.asm"),
@@ -117,10 +132,11 @@ fn _shuffle() -> Result<(), ()> {
         String::from("/dev/null"),
     ];
 
-    let clargs = clargs::parse(&args).map_err(stage::clargs)?;
+    let report = Report::new(false);
+    let clargs = clargs::parse(&args).map_err(|e| report.clargs(e))?;
 
-    let source = Source::new(clargs.path).map_err(stage::source)?;
-    let split = Split::new(source.main(), &clargs.symbols).map_err(stage::split)?;
+    let source = Source::new(clargs.path, &clargs.search_paths, Rc::default()).map_err(|e| report.source(ReadErr::new(clargs.path, e)))?;
+    let split = Split::new(source.main(), &clargs.symbols).map_err(|e| report.split(e))?;
     let words = split.words();
     
     let mut shuffled = String::new();
@@ -136,20 +152,39 @@ fn _shuffle() -> Result<(), ()> {
     }
 
     let source = Source::from_content(shuffled);
-    let split = Split::new(source.main(), &clargs.symbols).map_err(stage::split)?;
+    let split = Split::new(source.main(), &clargs.symbols).map_err(|e| report.split(e))?;
 
-    let parsed_tokens = prepare::parse(&split).map_err(stage::parse)?;
+    let parsed_tokens = prepare::parse(&split).map_err(|e| report.parse(e))?;
     let mut macros = Macros::new();
-    let mut ast = Ast::new(parsed_tokens, &mut macros, &source).map_err(stage::ast)?;
-    macros.expand(&mut ast).map_err(stage::macros)?;
-    let ast_ref = TokenRef::new(&ast);
-    ast_ref.validate().map_err(stage::ast_validation)?;
-    let op_map = OpMap::new(&ast_ref).map_err(stage::ops)?;
-    let mut constants = Constants::new(&ast_ref, &op_map).map_err(stage::constants)?;
-    let updates = constants.eval().map_err(stage::expressions)?;
+    let mut ast = Ast::new(parsed_tokens, &mut macros, &source).map_err(|e| report.ast(e))?;
+    macros.expand(&mut ast).map_err(|e| report.macros(e))?;
+    let ast_ref = TokenRef::new(&ast).map_err(|e| report.ast(vec![e]))?;
+    ast_ref.validate().map_err(|e| report.ast_validation(e))?;
+    let op_map = OpMap::new(&ast_ref).map_err(|e| report.ops(e))?;
+    let mut constants = Constants::new(&ast_ref, &op_map).map_err(|e| report.constants(e))?;
+    let updates = constants.eval().map_err(|e| report.expressions(e))?;
     constants.update(updates);
-    constants.validate(&ast_ref).map_err(stage::constants_validation)?;
-    encode::build(&clargs.output(), &ast_ref, &op_map, &constants).map_err(stage::encode)?;
+    constants.validate(&ast_ref).map_err(|e| report.constants_validation(e))?;
+    encode::build(&ast_ref, &op_map, &constants, true, None).map_err(|e| report.encode(e))?;
 
     Ok(())
 }
+
+/// The lines with errors are left out, and the errors of every stage are kept together.
+#[test]
+fn collect() {
+    let source = Source::from_content("#db A 1\nld q, 3\nnop\nld a, $+\n".into());
+    let (splits, errors) = Split::all(&source, &[]);
+    assert!(errors.is_empty());
+
+    let (parsed_tokens, errors) = prepare::parse_all(&source, &splits);
+    assert!(parsed_tokens.iter().all(|token| matches!(token.line_number, 1 | 3)));
+
+    let report = Report::new(true).with_source(&source);
+    let Failure{ lines } = report.parse(errors);
+    assert!(!lines.is_empty() && lines.iter().all(|(_, line)| matches!(line, 2 | 4)));
+    assert!(report.failed());
+
+    let mut macros = Macros::new();
+    assert!(Ast::new(parsed_tokens, &mut macros, &source).is_ok());
+}
@@ -0,0 +1,52 @@
+use crate::{
+    program::embed::assemble,
+    parse::provider::Memory,
+};
+
+const INPUT: &str = "
+#dw WRAM &C000
+#struct POINT X 1 Y 1
+#struct ACTOR POS POINT SPEED 2
+#ram WRAM
+#var TIMER 1
+#var ACTORS ACTOR 4
+#var END 1
+&0150:
+";
+
+#[test]
+fn layout() {
+    let assembly = assemble("main.gb.asm", INPUT, &[], Memory::new()).unwrap();
+    let symbol = |name| assembly.symbol(name).unwrap();
+
+    assert_eq!((symbol("POINT_Y"), symbol("ACTOR_SPEED"), symbol("ACTOR_SIZE")), (1, 2, 4));
+    assert_eq!((symbol("TIMER"), symbol("ACTORS"), symbol("ACTORS_SPEED")), (0xC000, 0xC001, 0xC003));
+    assert_eq!(symbol("END"), 0xC011);
+}
+
+#[test]
+fn width() {
+    let main = format!("{INPUT}    ld a (ACTORS_SPEED + 1)\n    ld a (ACTORS_SPEED)\n    ld (TIMER) sp\n");
+    let diagnostics = assemble("main.gb.asm", &main, &[], Memory::new()).unwrap_err();
+    let lines = diagnostics.iter().map(|d| (d.code.as_str(), d.line)).collect::<Vec<_>>();
+
+    assert_eq!(lines, [("FieldWidth", 11), ("FieldWidth", 12)]);
+}
+
+#[test]
+fn bad_declarations() {
+    let code = |main: &str| {
+        let diagnostics = assemble("main.gb.asm", &format!("{main}\n&0150:\n"), &[], Memory::new()).unwrap_err();
+        diagnostics.iter().map(|d| (d.code.clone(), d.line)).collect::<Vec<_>>()
+    };
+
+    let error = |code: &str, line| vec![(code.to_string(), line)];
+
+    assert_eq!(code("#struct"), error("BadField", 1));
+    assert_eq!(code("#struct 5 A 1"), error("BadField", 1));
+    assert_eq!(code("#struct EMPTY A 0"), error("BadField", 1));
+    assert_eq!(code("#ram &C000\n#var 5 1"), error("BadVar", 2));
+    assert_eq!(code("#ram &C000\n#var A 0"), error("BadVar", 2));
+    assert_eq!(code("#ram &C000\n#var A 1 0"), error("BadVar", 2));
+    assert_eq!(code("#ram &FFFF\n#var A 1\n#var B 2"), error("RamOverflow", 3));
+}
@@ -0,0 +1,38 @@
+use crate::program::style::format;
+
+#[test]
+fn format_source() {
+    let input = "\
+#db  LEN   4   ;Kept as is
+  :Start ;Entry
+ld  a ( hl )   ;Load
+   jp   NZ  Start
+
+  ;Comment
+#if GBC
+  ld a   b
+#endif
+#macro twice. .a
+add .a  .a
+#macro
+:Text \"a ;  b\"  0   \n\n";
+
+    let output = "\
+#db  LEN   4    ;Kept as is
+:Start          ;Entry
+    ld a (hl)   ;Load
+    jp NZ Start
+
+  ;Comment
+#if GBC
+  ld a   b
+#endif
+#macro twice. .a
+    add .a .a
+#macro
+:Text \"a ;  b\" 0
+";
+
+    assert_eq!(format(input), output);
+    assert_eq!(format(output), output);
+}
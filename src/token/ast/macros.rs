@@ -190,6 +190,7 @@ impl<'a, 'b> Macros {
     }
 
     /// Recursively traverse a macro declaration to expand a call into a separate Ast.
+    #[allow(clippy::too_many_arguments)]
     fn copy_decl(
         ast: &Ast<'a>,
         mut call_ast: Ast<'a>,
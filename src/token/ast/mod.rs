@@ -41,7 +41,7 @@ impl<'a> Ast<'a> {
         let root = Self::create_root();
         let mut ast = Self { source, tokens: vec![root] };
         let mut selection = 0;
-        let mut current_line = 0;
+        let mut current_line = ("", 0);
 
         for token in tokens {
             // `NamedMark` and `AnonMark` need one child.
@@ -51,8 +51,9 @@ impl<'a> Ast<'a> {
             }
 
             // Is the token on a new line.
-            if current_line != token.line_number {
-                current_line = token.line_number;
+            // Imported files are linked in the middle of others.
+            if current_line != (token.file, token.line_number) {
+                current_line = (token.file, token.line_number);
 
                 // Update selection after the end of a line.
                 if ast.newline(&mut selection, &mut errors).is_err() {
@@ -60,7 +61,10 @@ impl<'a> Ast<'a> {
                 }
             }
 
-            ast.process_token(token, &mut selection, macros);
+            if let Err(e) = ast.process_token(token, &mut selection, macros) {
+                errors.push(e);
+                return Err(errors);
+            }
         }
 
         // Run `newline` for the last line too.
@@ -90,7 +94,8 @@ impl<'a> Ast<'a> {
             let sel_ty = self.type_of(*selection);
 
             if fail_safe == 0 {
-                bug!("Could not process newline");
+                errors.push(bug!(AstMsg, err_ctx));
+                return Err(());
             }
 
             fail_safe -= 1;
@@ -100,7 +105,12 @@ impl<'a> Ast<'a> {
                     // If it's a macro declaration, add a new macro body.
                     Macro => {
                         let t = Self::empty(MacroBody, file, line_number, line);
-                        self.cascade(selection, &[], t, Some(0));        
+
+                        if let Err(e) = self.cascade(selection, &[], t, Some(0)) {
+                            errors.push(e);
+                            return Err(());
+                        }
+
                         break;
                     }
 
@@ -117,13 +127,42 @@ impl<'a> Ast<'a> {
                     }
 
                     Expr => {
-                        if let Err(e) = expr::build(self, *selection) {
+                        let directive = self.parent_of(*selection);
+                        let directive_ty = self.type_of(directive);
+
+                        if matches!(directive_ty, Assert|Print|Warn|Error) {
+                            self.directive_message(*selection);
+                        }
+
+                        // Only the expression of an assertion is required.
+                        if matches!(directive_ty, Print|Warn|Error)
+                        && self.tokens[*selection].children.is_empty() {
+                            let expr = *selection;
+                            self.tokens[directive].children.retain(|c| *c != expr);
+                        }
+
+                        else if let Err(e) = expr::build(self, *selection) {
                             errors.push(e);
                         }
 
                         self.up(selection);
                     }
 
+                    // The start and the step of an enum, and the values of data, can be expressions in parentheses.
+                    Enum|DataB|DataW|Fill => {
+                        let ats = self.tokens[*selection].children.iter().copied()
+                            .filter(|child| self.type_of(*child) == At)
+                            .collect::<Vec<_>>();
+
+                        for at in ats {
+                            if let Err(e) = expr::build(self, at) {
+                                errors.push(e);
+                            }
+                        }
+
+                        self.up(selection);
+                    }
+
                     _ => self.up(selection)
                 }
             }
@@ -142,13 +181,13 @@ impl<'a> Ast<'a> {
         token: ParsedToken<'a>,
         selection: &mut usize,
         macros: &mut Macros,
-    ) {
+    ) -> Result<(), AsmErr<'a, AstMsg>> {
         let ParsedToken{ file, line_number, line, .. } = token;
 
         // Match parent type of the token.
         match token.ty.parent_type() {
             p @ InstrName =>
-                self.cascade(selection, &[Instruction, p], token, Some(2)),
+                self.cascade(selection, &[Instruction, p], token, Some(2))?,
 
             p @ (Register|Flag|Lit) => {
                 if self.type_of(*selection) == Argument {
@@ -156,11 +195,11 @@ impl<'a> Ast<'a> {
                 }
 
                 if self.type_of(*selection) == Instruction {
-                    self.cascade(selection, &[Argument, p], token, None);
+                    self.cascade(selection, &[Argument, p], token, None)?;
                 }
 
                 else {
-                    self.cascade(selection, &[p], token, None);
+                    self.cascade(selection, &[p], token, None)?;
                     let s = *selection;
 
                     if self.px_ty(s, 0) == At && self.px_ty(s, 1) == Argument {
@@ -175,7 +214,7 @@ impl<'a> Ast<'a> {
                     }
 
                     else {
-                        bug!("Invalid token after Marker");
+                        return Err(err!(AstMsg, MarkWithoutLiteral, (&token).into()));
                     }
                 }
             }
@@ -183,12 +222,12 @@ impl<'a> Ast<'a> {
             Macro => {
                 // Is it a macro call? 
                 if token.ty == MacroIdent && self.type_of(*selection) != Macro {
-                    self.cascade(selection, &[MacroCall], token, Some(1));
+                    self.cascade(selection, &[MacroCall], token, Some(1))?;
                     macros.calls.push(*selection);
                 }
 
                 else {
-                    self.cascade(selection, &[], token, None);
+                    self.cascade(selection, &[], token, None)?;
                 }
             }
 
@@ -199,20 +238,20 @@ impl<'a> Ast<'a> {
                     }
 
                     if self.type_of(*selection) == Instruction {
-                        self.cascade(selection, &[Argument], token, None);
+                        self.cascade(selection, &[Argument], token, None)?;
                     }
 
                     else {
-                        self.cascade(selection, &[], token, None);
+                        self.cascade(selection, &[], token, None)?;
                         let s = *selection;
 
                         if self.px_ty(s, 0) == At && self.px_ty(s, 1) == Argument {
                             self.up(selection);
                         }
                         
-                        else if matches!(self.type_of(*selection), DefB|DefW) {
+                        else if matches!(self.type_of(*selection), DefB|DefW|Load) {
                             let t = Self::empty(Expr, file, line_number, line);
-                            self.cascade(selection, &[], t, Some(0));
+                            self.cascade(selection, &[], t, Some(0))?;
                         }
                     }
                 }
@@ -222,11 +261,11 @@ impl<'a> Ast<'a> {
                     let at = Self::empty(At, file, line_number, line);
 
                     if self.type_of(*selection) == Instruction {
-                        self.cascade(selection, &[Argument], at, Some(0));
+                        self.cascade(selection, &[Argument], at, Some(0))?;
                     }
 
                     else {
-                        self.cascade(selection, &[], at, Some(0));
+                        self.cascade(selection, &[], at, Some(0))?;
                     }
                 }
 
@@ -245,19 +284,43 @@ impl<'a> Ast<'a> {
 
                     else {
                         let t = Self::empty(Macro, file, line_number, line);
-                        self.cascade(selection, &[], t, Some(0));
+                        self.cascade(selection, &[], t, Some(0))?;
                         macros.decls.push(*selection);
                     }
                 }
 
-                ty@(DefB|DefW|Include|Import|AnonMark|NamedMark) => 
-                    self.cascade(selection, &[ty.parent_type()], token, Some(0)),
+                ty@(DefB|DefW|Include|Import|Rle|Lz|Cycles|Struct|Var|Enum|DataB|DataW|Fill|Load|EndLoad|Align|Aligned|AnonMark|NamedMark) => 
+                    self.cascade(selection, &[ty.parent_type()], token, Some(0))?,
+
+                // The expression starts right after the directive.
+                Assert|Print|Warn|Error|Ram => {
+                    self.cascade(selection, &[Directive], token, Some(0))?;
+                    let t = Self::empty(Expr, file, line_number, line);
+                    self.cascade(selection, &[], t, Some(0))?;
+                }
 
-                Label => self.cascade(selection, &[Marker], token, None),
+                Label => self.cascade(selection, &[Marker], token, None)?,
 
-                _ => self.cascade(selection, &[], token, None),
+                _ => self.cascade(selection, &[], token, None)?,
             }
         }
+
+        Ok(())
+    }
+
+    /// Moves the string literal before or after the expression of a directive into the directive.
+    fn directive_message(&mut self, expr: usize) {
+        let children = &self.tokens[expr].children;
+        let ends = [children.first(), children.last()];
+
+        let message = ends.into_iter().flatten().copied().find(|end| {
+            self.type_of(*end) == Lit
+            && self.tokens[*end].children.first().is_some_and(|lit| self.type_of(*lit) == LitStr)
+        });
+
+        if let Some(message) = message {
+            self.move_into(message, self.parent_of(expr));
+        }
     }
 
     pub(super) fn get_root(&self) -> &Token<'a> {
@@ -286,8 +349,9 @@ impl<'a> Ast<'a> {
         parent_types: &[TokenType], 
         token: ParsedToken<'a>,
         select_up: Option<usize>,
-    ) {
+    ) -> Result<(), AsmErr<'a, AstMsg>> {
         let ParsedToken{ file, line_number, line, .. } = token;
+        let err_ctx = (&token).into();
 
         let mut selection = *dest;
         let mut inserts = vec![];
@@ -302,12 +366,10 @@ impl<'a> Ast<'a> {
         inserts.push(self.tokens.len()-1);
 
         if let Some(select_up) = select_up {
-            inserts.iter().rev().nth(select_up).map_or_else(|| {
-                bug!("Trying to select a parent token too far up.");
-            }, |i| {
-                *dest = *i;
-            });
+            *dest = *inserts.iter().rev().nth(select_up).ok_or(bug!(AstMsg, err_ctx))?;
         }
+
+        Ok(())
     }
 
     /// Sets index to the index of the parent.
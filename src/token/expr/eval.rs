@@ -30,7 +30,7 @@ impl ExprResult {
                     let expr_value = match expr.parent().ty() {
                         DefB => ExprValue::U8(v as u8),
                         DefW => ExprValue::U16(v as u16),
-                        _ => bug!("Wrong Def type.")
+                        _ => return Err(vec![bug!(ExprMsg, expr.into())]),
                     };
 
                     updates.push((s, expr_value));
@@ -45,6 +45,60 @@ impl ExprResult {
 
 }
 
+/// Evaluates an expression outside of a constant, once all the constants have a value.
+/// Intermediate and final results can be negative.
+pub fn value<'a>(
+    expr: &'a TokenRef<'a>,
+    constants: &'a Constants<'a>,
+) -> Result<isize, Vec<AsmErr<'a, ExprMsg>>> {
+    match ExprCtx::new(constants).eval_scope(expr) {
+        Ok((value, _)) => Ok(value),
+        Err(ctx) => Err(ctx.errors),
+    }
+}
+
+/// Evaluates the address of a variable, `offset` bytes after the start of its RAM section.
+pub fn address<'a>(
+    section: &'a TokenRef<'a>,
+    offset: usize,
+    constants: &'a Constants<'a>,
+) -> Result<ExprValue, Vec<AsmErr<'a, ExprMsg>>> {
+    match ExprCtx::new(constants).eval_section(section, offset) {
+        Ok((value, _)) => Ok(ExprValue::U16(value as u16)),
+        Err(ctx) => Err(ctx.errors),
+    }
+}
+
+/// Checks that a variable ending `end` bytes after the start of its RAM section fits below &FFFF.
+/// Errors of the section's expression are left to the evaluation of the variable's address.
+pub fn ram_end<'a>(
+    var: &'a TokenRef<'a>,
+    section: &'a TokenRef<'a>,
+    end: usize,
+    constants: &'a Constants<'a>,
+) -> Result<(), AsmErr<'a, ExprMsg>> {
+    match ExprCtx::new(constants).eval_section(section, end) {
+        Ok((value, _)) if value > 0x10000 => Err(err!(ExprMsg, RamOverflow, var.into())),
+        _ => Ok(()),
+    }
+}
+
+/// Evaluates the value at `index` in an enum, that must fit in a byte.
+pub fn member<'a>(
+    name: &'a TokenRef<'a>,
+    start: Option<&'a TokenRef<'a>>,
+    step: Option<&'a TokenRef<'a>>,
+    index: usize,
+    constants: &'a Constants<'a>,
+) -> Result<ExprValue, Vec<AsmErr<'a, ExprMsg>>> {
+    match ExprCtx::new(constants).eval_member(start, step, index) {
+        Ok((value, _)) if value < 0 => Err(vec![err!(ExprMsg, NegativeResult, name.into())]),
+        Ok((value, _)) => u8::try_from(value).map(ExprValue::U8)
+            .map_err(|_| vec![err!(ExprMsg, EnumOverflow, name.into())]),
+        Err(ctx) => Err(ctx.errors),
+    }
+}
+
 /// Value resulting of an expression, can be a byte or a word.
 #[derive(Copy, Clone)]
 pub enum ExprValue {
@@ -89,7 +143,11 @@ impl<'a> ExprCtx<'a> {
         let result = match expr.parent().ty() {
             DefB => result % 256,
             DefW => result % 65536,
-            _ => bug!("Wrong Def type.")
+
+            _ => {
+                self.errors.push(bug!(ExprMsg, expr.into()));
+                return Err(self);
+            }
         };
 
         let ident = expr.parent().first().value().as_str().unwrap().to_string();
@@ -98,6 +156,53 @@ impl<'a> ExprCtx<'a> {
         Ok((result, self))
     }
 
+    /// Evaluates a value that other constants are calculated from,
+    /// such as the start of a RAM section, which can not depend on them.
+    fn eval_dependency(mut self, scope: &'a TokenRef<'a>) -> Result<(isize, Self), Self> {
+        if self.dependencies.iter().any(|dep| **dep == *scope) {
+            self.errors.push(err!(ExprMsg, CircularDependency, scope.into()));
+            return Err(self);
+        }
+
+        self.dependencies.push(scope);
+
+        let (value, mut s) = self.eval_scope(scope)?;
+        s.dependencies.pop();
+        Ok((value, s))
+    }
+
+    fn eval_member(
+        self,
+        start: Option<&'a TokenRef<'a>>,
+        step: Option<&'a TokenRef<'a>>,
+        index: usize,
+    ) -> Result<(isize, Self), Self> {
+        let (start, s) = match start {
+            Some(start) => self.eval_dependency(start)?,
+            None => (0, self),
+        };
+
+        let (step, s) = match step {
+            Some(step) => s.eval_dependency(step)?,
+            None => (1, s),
+        };
+
+        Ok((start + index as isize * step, s))
+    }
+
+    fn eval_section(self, section: &'a TokenRef<'a>, offset: usize) -> Result<(isize, Self), Self> {
+        match self.eval_dependency(section) {
+            Ok((value, s)) if value >= 0 => Ok((value + offset as isize, s)),
+
+            Ok((_, mut s)) => {
+                s.errors.push(err!(ExprMsg, NegativeResult, section.into()));
+                Err(s)
+            }
+
+            Err(s) => Err(s)
+        }
+    }
+
     fn eval_scope(mut self, scope: &'a TokenRef<'a>) -> Result<(isize, Self), Self> {
         let children = scope.children();
 
@@ -113,7 +218,10 @@ impl<'a> ExprCtx<'a> {
                         Err(self)
                     }
 
-                    _ => bug!("Unhandled Lit type in Expr")
+                    _ => {
+                        self.errors.push(bug!(ExprMsg, litx.into()));
+                        Err(self)
+                    }
                 }
             }
 
@@ -141,7 +249,10 @@ impl<'a> ExprCtx<'a> {
                                 Err(self)
                             }
 
-                            _ => bug!("Unhandled value type")
+                            _ => {
+                                self.errors.push(bug!(ExprMsg, scope.into()));
+                                Err(self)
+                            }
                         }
                     }
 
@@ -153,13 +264,20 @@ impl<'a> ExprCtx<'a> {
                             }
                         }
 
-                        return match self.evaluate(expr) {
+                        match self.evaluate(expr) {
                             Ok((value, s)) => Ok((value as isize, s)),
                             Err(s) => Err(s)
-                        };
+                        }
                     }
 
-                    _ => bug!("Invalid constant")
+                    ConstExpr::Offset{ section, offset } => self.eval_section(section, *offset),
+
+                    ConstExpr::Enum{ start, step, index, .. } => self.eval_member(*start, *step, *index),
+
+                    ConstExpr::Mark => {
+                        self.errors.push(bug!(ExprMsg, scope.into()));
+                        Err(self)
+                    }
                 }
             }
 
@@ -183,7 +301,8 @@ impl<'a> ExprCtx<'a> {
                     return self.eval_scope(child);
                 }
 
-                bug!("Unexpected token in expression.");
+                self.errors.push(bug!(ExprMsg, scope.into()));
+                Err(self)
             }
         }
     }
@@ -208,7 +327,7 @@ impl<'a> ExprCtx<'a> {
     }
 
     fn eval_op(
-        self, 
+        mut self, 
         op: &'a TokenRef<'a>,
     ) -> Result<(isize, Self), Self> {
         assert_eq!(op.ty().parent_type(), Expr);
@@ -233,7 +352,17 @@ impl<'a> ExprCtx<'a> {
             BinAnd => self.eval_bin(|lhs, rhs| lhs & rhs, op),
             BinXor => self.eval_bin(|lhs, rhs| lhs ^ rhs, op),
             BinOr => self.eval_bin(|lhs, rhs| lhs | rhs, op),
-            _ => bug!("Unhandled operator type")
+            BinEq => self.eval_bin(|lhs, rhs| (lhs == rhs) as isize, op),
+            BinNe => self.eval_bin(|lhs, rhs| (lhs != rhs) as isize, op),
+            BinLt => self.eval_bin(|lhs, rhs| (lhs < rhs) as isize, op),
+            BinLe => self.eval_bin(|lhs, rhs| (lhs <= rhs) as isize, op),
+            BinGt => self.eval_bin(|lhs, rhs| (lhs > rhs) as isize, op),
+            BinGe => self.eval_bin(|lhs, rhs| (lhs >= rhs) as isize, op),
+
+            _ => {
+                self.errors.push(bug!(ExprMsg, op.into()));
+                Err(self)
+            }
         }
     }
 
@@ -34,9 +34,10 @@ const PRECEDENCE: &[Prec] = &[
     Prec::new(Binary, &[BinAdd, BinSub]),
     Prec::new(Binary, &[BinShl, BinShr]),
     Prec::new(Binary, &[BinAnd, BinXor, BinOr]),
+    Prec::new(Binary, &[BinEq, BinNe, BinLt, BinLe, BinGt, BinGe]),
 ];
 
-/// Builds an `Expr` token from a `DefB` or `DefW`.
+/// Builds an `Expr` token from a `DefB`, `DefW` or `Assert`.
 pub fn build<'a>(ast: &mut Ast<'a>, scope: usize) -> Result<(), AsmErr<'a, AstMsg>> {
     if cfg!(test) {
         return Ok(());
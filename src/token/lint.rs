@@ -0,0 +1,248 @@
+use crate::{
+    parse::{ lex::TokenType::*, library },
+    token::{ ast::macros::Macros, read::TokenRef },
+    error::asm::{ AsmErr, LintMsg::{self, *} },
+};
+
+use std::collections::HashSet;
+
+/// What to do when a lint finds something.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// Level of each lint, all of them warn by default.
+#[derive(Debug, Clone)]
+pub struct Lints {
+    levels: [Level; LintMsg::ALL.len()],
+}
+
+impl Default for Lints {
+    fn default() -> Self {
+        Self{ levels: [Level::Warn; LintMsg::ALL.len()] }
+    }
+}
+
+impl Lints {
+
+    /// Sets the level of a lint by its name, or of all of them with `all`.
+    pub fn set(&mut self, name: &str, level: Level) -> Result<(), ()> {
+        if name == "all" {
+            self.levels = [level; LintMsg::ALL.len()];
+            return Ok(());
+        }
+
+        let i = LintMsg::ALL.iter().position(|lint| lint.name() == name).ok_or(())?;
+        self.levels[i] = level;
+
+        Ok(())
+    }
+
+    pub fn level(&self, lint: LintMsg) -> Level {
+        let i = LintMsg::ALL.iter().position(|l| *l == lint).unwrap();
+        self.levels[i]
+    }
+
+    /// Separates the lints that are errors from the warnings.
+    pub fn partition<'a>(
+        &self,
+        found: Vec<AsmErr<'a, LintMsg>>,
+    ) -> (Vec<AsmErr<'a, LintMsg>>, Vec<AsmErr<'a, LintMsg>>) {
+        found.into_iter().partition(|lint| self.level(lint.ty) == Level::Deny)
+    }
+
+}
+
+/// Runs the lints that are not allowed on the expanded token tree.
+/// Files from the standard library are not checked.
+pub fn check<'a>(
+    root: &'a TokenRef<'a>,
+    macros: &Macros,
+    lints: &Lints,
+) -> Vec<AsmErr<'a, LintMsg>> {
+    let mut found = vec![];
+
+    let mut flow = Flow::default();
+    flow.walk(root, &mut found);
+
+    let mut used = HashSet::new();
+    identifiers(root, &mut used);
+    unused(root, &used, &mut found);
+    unused_macros(root, macros, &mut found);
+
+    found.retain(|lint| lints.level(lint.ty) != Level::Allow && !library::is_library(lint.err_ctx.file()));
+    found
+}
+
+/// State of the execution, following the tokens in the order they are written.
+#[derive(Default)]
+struct Flow<'a> {
+    /// The last instruction was an unconditional jump or return.
+    stopped: bool,
+    /// The last instruction continues into the next token.
+    falls: bool,
+    /// Label reached by falling from an instruction, with nothing after it yet.
+    label: Option<&'a TokenRef<'a>>,
+}
+
+impl<'a> Flow<'a> {
+
+    fn walk(&mut self, root: &'a TokenRef<'a>, found: &mut Vec<AsmErr<'a, LintMsg>>) {
+        for token in root.children() {
+            match token.ty() {
+                MacroCall => {
+                    if let Some(body) = token.try_first_of(MacroBody) {
+                        self.walk(body, found);
+                    }
+                }
+
+                Instruction => self.instruction(token, found),
+                Lit|Identifier => self.data(found),
+
+                Directive => {
+                    if matches!(token.first().ty(), Include|Rle|Lz|DataB|DataW|Fill) {
+                        self.data(found);
+                    }
+                }
+
+                Marker => {
+                    let mark = token.first();
+
+                    if self.falls && matches!(mark.ty(), Label|NamedMark) {
+                        self.label = Some(mark);
+                    }else {
+                        self.falls = false;
+                        self.label = None;
+                    }
+
+                    self.stopped = false;
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn instruction(&mut self, token: &'a TokenRef<'a>, found: &mut Vec<AsmErr<'a, LintMsg>>) {
+        // Only the first unreachable instruction is reported.
+        if self.stopped {
+            found.push(err!(LintMsg, Unreachable, token.into()));
+        }
+
+        if is_self_load(token) {
+            found.push(err!(LintMsg, SelfLoad, token.into()));
+        }
+
+        let stops = stops_flow(token);
+        self.stopped = stops;
+        self.falls = !stops;
+        self.label = None;
+    }
+
+    fn data(&mut self, found: &mut Vec<AsmErr<'a, LintMsg>>) {
+        if let Some(label) = self.label.take() {
+            found.push(err!(LintMsg, FallThrough, label.into()));
+        }
+
+        self.stopped = false;
+        self.falls = false;
+    }
+
+}
+
+/// Unconditional `jp`, `jr`, `ret` and `reti`.
+fn stops_flow(instruction: &TokenRef) -> bool {
+    let args = instruction.children().iter().filter(|child| child.ty() == Argument).count();
+
+    match instruction.try_first_of(InstrName).map(|name| name.first().ty()) {
+        Some(Jp|Jr) => args == 1,
+        Some(Ret) => args == 0,
+        Some(Reti) => true,
+        _ => false,
+    }
+}
+
+/// `ld` with the same register on both sides.
+fn is_self_load(instruction: &TokenRef) -> bool {
+    if instruction.try_first_of(InstrName).map(|name| name.first().ty()) != Some(Ld) {
+        return false;
+    }
+
+    let registers = instruction.children().into_iter()
+        .filter(|child| child.ty() == Argument)
+        .map(|arg| arg.first())
+        .filter(|arg| arg.ty() == Register)
+        .map(|register| register.first().ty())
+        .collect::<Vec<_>>();
+
+    registers.len() == 2 && registers[0] == registers[1]
+}
+
+/// Collects the names of all the identifiers in use.
+fn identifiers<'a>(token: &'a TokenRef<'a>, used: &mut HashSet<&'a str>) {
+    // The first identifier of a definition is its name, the identifiers of a struct are its fields.
+    let skip = match token.ty() {
+        DefB|DefW|Rle|Lz|Var|Load => 1,
+        Struct => usize::MAX,
+        _ => 0,
+    };
+
+    for child in token.children().into_iter().skip(skip) {
+        // Names of an enum are outside of the parentheses of its start and step.
+        if child.ty() == Identifier && token.ty() != Enum {
+            if let Ok(ident) = child.value().as_str() {
+                used.insert(ident);
+            }
+        }
+
+        identifiers(child, used);
+    }
+}
+
+/// Labels and constants defined at the top level that are never used.
+fn unused<'a>(
+    root: &'a TokenRef<'a>,
+    used: &HashSet<&'a str>,
+    found: &mut Vec<AsmErr<'a, LintMsg>>,
+) {
+    for token in root.children() {
+        let (ident, lint) = match token.ty() {
+            Marker if matches!(token.first().ty(), Label|NamedMark) => (token.first(), UnusedLabel),
+
+            // The size given by `#rle` and `#lz` is part of their syntax.
+            Directive if matches!(token.first().ty(), DefB|DefW) => (token.first().first(), UnusedConstant),
+
+            _ => continue,
+        };
+
+        if ident.value().as_str().is_ok_and(|name| !used.contains(name)) {
+            found.push(err!(LintMsg, lint, ident.into()));
+        }
+    }
+}
+
+/// Macros that are declared but never called.
+fn unused_macros<'a>(
+    root: &'a TokenRef<'a>,
+    macros: &Macros,
+    found: &mut Vec<AsmErr<'a, LintMsg>>,
+) {
+    let tokens = &root.ast().tokens;
+    let ident = |i: usize| tokens[i].children.first().map(|ident| &tokens[*ident]);
+
+    let called = macros.calls.iter()
+        .filter_map(|call| ident(*call))
+        .filter_map(|ident| ident.value.as_str().ok())
+        .collect::<HashSet<_>>();
+
+    for decl in &macros.decls {
+        let Some(ident) = ident(*decl) else { continue };
+
+        if ident.value.as_str().is_ok_and(|name| !called.contains(name)) {
+            found.push(err!(LintMsg, UnusedMacro, ident.into()));
+        }
+    }
+}
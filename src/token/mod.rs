@@ -8,6 +8,9 @@ pub mod expr;
 /// Tools to make reading the token tree easier.
 pub mod read;
 
+/// Warnings for code that builds but is likely a mistake.
+pub mod lint;
+
 use crate::{
     parse::{ lex::TokenType, prepare::ParsedToken },
     write::encode,
@@ -53,7 +56,7 @@ impl<'a> Value<'a> {
             Self::U8(v) => Ok(vec![*v]),
             Self::U16(v) => Ok(encode::u16_to_bytes(*v)),
             Self::Str(v) => encode::str_to_bytes(v),
-            _ => bug!("Wrong value type")
+            _ => Err(()),
         }
     }
 
@@ -4,7 +4,7 @@
 use crate::{
     parse::lex::TokenType,
     token::{ Token, Value, ast::Ast },
-    error::{ ITERATION_LIMIT, asm::{ AsmErr, AstValidationMsg::{self, *} } },
+    error::{ ITERATION_LIMIT, asm::{ AsmErr, AstMsg, AstValidationMsg::{self, *} } },
 };
 
 /// Read-only reference to a token.
@@ -28,26 +28,29 @@ impl<'a> Eq for TokenRef<'a> {}
 impl<'a> TokenRef<'a> {
 
     /// Creates a `TokenRef` from the root token of an `Ast`.
-    pub fn new(ast: &'a Ast) -> Self {
+    /// Fails when the tree is deeper than `ITERATION_LIMIT`.
+    pub fn new(ast: &'a Ast) -> Result<Self, AsmErr<'a, AstMsg>> {
         let root = ast.get_root();
         let mut current = Self{ ast, token: root, parent: std::ptr::null(), children: vec![] };
         current.parent = &current;
-        Self::walk(ast, &mut current, 0); 
+        Self::walk(ast, &mut current, 0)?;
 
-        current
+        Ok(current)
     }
 
-    fn walk(ast: &'a Ast, current: &mut Self, fail_safe: usize) {
+    fn walk(ast: &'a Ast, current: &mut Self, fail_safe: usize) -> Result<(), AsmErr<'a, AstMsg>> {
         if fail_safe >= ITERATION_LIMIT {
-            bug!("Recursion limit reached while building TokenRef tree.");
+            return Err(bug!(AstMsg, current.token.into()));
         }
 
         for child in &current.token.children {
             let token = &ast.tokens[*child];
             let token_ref = Self{ ast, token, parent: current, children: vec![] };
             current.children.push(token_ref);
-            Self::walk(ast, current.children.last_mut().unwrap(), fail_safe + 1);
+            Self::walk(ast, current.children.last_mut().unwrap(), fail_safe + 1)?;
         }
+
+        Ok(())
     }
 
     /// Returns a reference to the child `TokenRef` at specified index.
@@ -62,13 +65,14 @@ impl<'a> TokenRef<'a> {
         self.children.get(child)
     }
 
-    pub const fn ast(&self) -> &Ast { self.ast }
+    pub const fn ast(&self) -> &'a Ast<'a> { self.ast }
     pub const fn token(&self) -> &Token<'a> { self.token }
     pub const fn ty(&self) -> TokenType { self.token.ty }
+    pub const fn file(&self) -> &'a str { self.token.file }
     pub const fn line_number(&self) -> usize { self.token.line_number }
     pub const fn line(&self) -> &'a str { self.token.line }
     pub const fn word(&self) -> &'a str { self.token.word }
-    pub const fn value(&self) -> &Value { &self.token.value }
+    pub const fn value(&self) -> &Value<'_> { &self.token.value }
     pub const fn index(&self) -> usize { self.token.index }
 
     /// Returns a reference to the parent `TokenRef` or `self` if it is the root.
@@ -100,19 +104,6 @@ impl<'a> TokenRef<'a> {
         self.get(0)
     }
 
-    /// Finds the first child of specified type.
-    /// Panics:
-    /// The token is not found.
-    pub fn first_of(&self, ty: TokenType) -> &Self {
-        for child in &self.children {
-            if child.ty() == ty {
-                return child;
-            }
-        }
-
-        bug!("Could not find Token by type.");
-    }
-
     /// Finds the first child of specified type.
     pub fn try_first_of(&self, ty: TokenType) -> Option<&Self> {
         self.children.iter().find(|&child| child.ty() == ty)
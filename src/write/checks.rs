@@ -0,0 +1,111 @@
+use crate::{
+    write::{ constants::Constants, data },
+    token::{ read::TokenRef, expr::eval },
+    parse::lex::TokenType::*,
+    error::asm::{ AsmErr, CheckMsg },
+};
+
+/// Results of the `#assert`, `#print`, `#warn` and `#error` directives.
+#[derive(Default)]
+pub struct Checks<'a> {
+    /// Directive and the text to print.
+    pub prints: Vec<(&'a TokenRef<'a>, String)>,
+    pub warnings: Vec<AsmErr<'a, CheckMsg<'a>>>,
+    pub errors: Vec<AsmErr<'a, CheckMsg<'a>>>,
+}
+
+/// Evaluates the directives once all the constants have a value, in the order they are written.
+/// Directives from macro bodies are reported at the macro call.
+/// - `#assert` fails when its expression is 0.
+/// - `#warn` and `#error` report their message when their expression is not 0, or when they have none.
+/// - `#byte`, `#word` and `#fill` fail when a value does not fit in their width.
+pub fn check<'a>(root: &'a TokenRef<'a>, constants: &'a Constants<'a>) -> Checks<'a> {
+    let mut checks = Checks::default();
+    checks.walk(root, constants);
+    checks
+}
+
+impl<'a> Checks<'a> {
+
+    fn walk(&mut self, scope: &'a TokenRef<'a>, constants: &'a Constants<'a>) {
+        for token in scope.children() {
+            match token.ty() {
+                MacroCall => {
+                    if let Some(body) = token.try_first_of(MacroBody) {
+                        self.walk(body, constants);
+                    }
+                }
+
+                Directive if matches!(token.first().ty(), Assert|Print|Warn|Error) => {
+                    self.directive(token.first(), constants);
+                }
+
+                Directive if matches!(token.first().ty(), DataB|DataW|Fill) => {
+                    if let Err(e) = data::bytes(token.first(), constants) {
+                        self.errors.extend(e.into_iter().map(|e| AsmErr{
+                            ty: CheckMsg::Expr(e.ty),
+                            err_ctx: e.err_ctx,
+                            source_ctx: e.source_ctx,
+                        }));
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn directive(&mut self, directive: &'a TokenRef<'a>, constants: &'a Constants<'a>) {
+        let message = directive.try_first_of(Lit).and_then(|lit| lit.first().value().as_str().ok());
+        let expr = directive.try_first_of(Expr);
+
+        let value = match expr.map(|expr| eval::value(expr, constants)) {
+            Some(Ok(value)) => Some(value),
+            None => None,
+
+            Some(Err(e)) => {
+                self.errors.extend(e.into_iter().map(|e| AsmErr{
+                    ty: CheckMsg::Expr(e.ty),
+                    err_ctx: e.err_ctx,
+                    source_ctx: e.source_ctx,
+                }));
+
+                return;
+            }
+        };
+
+        if directive.ty() == Print {
+            let text = match (message, value) {
+                (Some(message), Some(value)) => format!("{message} {value}"),
+                (Some(message), None) => message.to_string(),
+                (None, Some(value)) => value.to_string(),
+                (None, None) => String::new(),
+            };
+
+            self.prints.push((directive, text));
+            return;
+        }
+
+        let Some(message) = message else {
+            self.errors.push(err!(CheckMsg, CheckMsg::NoMessage, directive.into()));
+            return;
+        };
+
+        match directive.ty() {
+            Assert if value == Some(0) => {
+                self.errors.push(err!(CheckMsg, CheckMsg::Assert(message), directive.into()));
+            }
+
+            Warn if value != Some(0) => {
+                self.warnings.push(err!(CheckMsg, CheckMsg::Warning(message), directive.into()));
+            }
+
+            Error if value != Some(0) => {
+                self.errors.push(err!(CheckMsg, CheckMsg::Error(message), directive.into()));
+            }
+
+            _ => {}
+        }
+    }
+
+}
@@ -0,0 +1,152 @@
+// Both formats are a list of packets ending with a 0 byte.
+// The routines to decompress them are in `std/decompress.gb.asm`.
+//
+// RLE packets:
+// - `0x01..=0x7F`     Copy the next N bytes.
+// - `0x80..=0xFF`     Repeat the next byte (N & 0x7F) + 2 times.
+//
+// LZ packets:
+// - `0x01..=0x7F`     Copy the next N bytes.
+// - `0x80..=0xFF`     Copy (N & 0x7F) + 3 bytes from the output, starting at
+//                     the distance given by the next 2 bytes (little-endian).
+
+/// End of the compressed stream.
+const END: u8 = 0;
+
+/// Longest run of literals in one packet.
+const MAX_LITERALS: usize = 0x7F;
+
+const RLE_MIN_RUN: usize = 2;
+const RLE_MAX_RUN: usize = 0x7F + RLE_MIN_RUN;
+
+const LZ_MIN_MATCH: usize = 3;
+const LZ_MAX_MATCH: usize = 0x7F + LZ_MIN_MATCH;
+const LZ_MAX_DISTANCE: usize = u16::MAX as usize;
+
+/// Number of previous positions checked when looking for a match.
+const LZ_CANDIDATES: usize = 64;
+
+/// Compresses data with run-length encoding.
+pub fn rle(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut literals = vec![];
+    let mut i = 0;
+
+    while i < data.len() {
+        let mut run = 1;
+
+        while i + run < data.len() && data[i + run] == data[i] && run < RLE_MAX_RUN {
+            run += 1;
+        }
+
+        // A run of 2 is only worth a packet when there are no pending literals.
+        if run >= 3 || (run == RLE_MIN_RUN && literals.is_empty()) {
+            push_literals(&mut out, &mut literals);
+            out.push(0x80 | (run - RLE_MIN_RUN) as u8);
+            out.push(data[i]);
+            i += run;
+        }
+
+        else {
+            literals.push(data[i]);
+            i += 1;
+
+            if literals.len() == MAX_LITERALS {
+                push_literals(&mut out, &mut literals);
+            }
+        }
+    }
+
+    push_literals(&mut out, &mut literals);
+    out.push(END);
+
+    out
+}
+
+/// Compresses data by referencing sequences that were already written.
+pub fn lz(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut literals = vec![];
+    // Positions of previous occurences of each 3 bytes sequence, latest last.
+    let mut chains = std::collections::HashMap::<[u8; 3], Vec<usize>>::new();
+    let mut i = 0;
+
+    while i < data.len() {
+        let (len, distance) = find_match(data, i, &chains);
+
+        let step = if len >= LZ_MIN_MATCH {
+            push_literals(&mut out, &mut literals);
+            out.push(0x80 | (len - LZ_MIN_MATCH) as u8);
+            out.extend_from_slice(&(distance as u16).to_le_bytes());
+            len
+        }
+
+        else {
+            literals.push(data[i]);
+
+            if literals.len() == MAX_LITERALS {
+                push_literals(&mut out, &mut literals);
+            }
+
+            1
+        };
+
+        for pos in i..i + step {
+            if let Some(key) = data.get(pos..pos + 3) {
+                chains.entry([key[0], key[1], key[2]]).or_default().push(pos);
+            }
+        }
+
+        i += step;
+    }
+
+    push_literals(&mut out, &mut literals);
+    out.push(END);
+
+    out
+}
+
+/// Finds the longest previous sequence matching the data at `i`.
+/// Returns its length and its distance from `i`.
+fn find_match(
+    data: &[u8],
+    i: usize,
+    chains: &std::collections::HashMap<[u8; 3], Vec<usize>>,
+) -> (usize, usize) {
+    let Some(key) = data.get(i..i + 3) else {
+        return (0, 0);
+    };
+
+    let Some(positions) = chains.get(&[key[0], key[1], key[2]]) else {
+        return (0, 0);
+    };
+
+    let mut best = (0, 0);
+
+    for pos in positions.iter().rev().take(LZ_CANDIDATES) {
+        if i - pos > LZ_MAX_DISTANCE {
+            break;
+        }
+
+        // The match can overlap the current position.
+        let len = data[i..].iter().zip(&data[*pos..])
+            .take(LZ_MAX_MATCH)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        if len > best.0 {
+            best = (len, i - pos);
+        }
+    }
+
+    best
+}
+
+fn push_literals(out: &mut Vec<u8>, literals: &mut Vec<u8>) {
+    if literals.is_empty() {
+        return;
+    }
+
+    out.push(literals.len() as u8);
+    out.append(literals);
+}
@@ -1,7 +1,7 @@
 
 use crate::{
     parse::{ lex::TokenType::* },
-    token::{ Value, read::TokenRef, expr::eval::{ExprResult, ExprValue } },
+    token::{ Value, read::TokenRef, expr::eval::{ self, ExprResult, ExprValue } },
     error::{ 
         ITERATION_LIMIT, 
         asm::{ 
@@ -11,13 +11,13 @@ use crate::{
             ConstantsValidationMsg::{self, *} 
         },
     },
-    write::ops::OpMap,
+    write::{ ops::OpMap, compress, data },
 };
 
 #[cfg(debug_assertions)]
 use crate::program::fmt::title;
 
-use std::collections::HashMap;
+use std::{ borrow::Cow, collections::HashMap };
 
 /// Holds the value of a constant or the token required to calculate it.
 #[derive(Copy, Clone)]
@@ -28,6 +28,11 @@ pub enum ConstExpr<'a> {
     Value(Value<'a>),
     /// Value needs to be calculated.
     Expr(&'a TokenRef<'a>),
+    /// Address of a variable, `offset` bytes after the start of its RAM section,
+    /// once the expression of the section is calculated.
+    Offset{ section: &'a TokenRef<'a>, offset: usize },
+    /// Byte of the value at `index` in an enum, from its start and step or from 0 by 1.
+    Enum{ name: &'a TokenRef<'a>, start: Option<&'a TokenRef<'a>>, step: Option<&'a TokenRef<'a>>, index: usize },
 }
 
 impl<'a> ConstExpr<'a> {
@@ -38,9 +43,26 @@ impl<'a> ConstExpr<'a> {
 
 }
 
+/// Fields of a struct with their size, in order.
+pub type Fields<'a> = Vec<(&'a str, usize)>;
+
 pub struct Constants<'a> {
-    pub const_exprs: Vec<(&'a str, ConstExpr<'a>)>,
-    pub includes: HashMap<&'a str, Vec<u8>>,
+    /// Constants generated by `Struct` and `Var` directives have a name that is not in the source.
+    pub const_exprs: Vec<(Cow<'a, str>, ConstExpr<'a>)>,
+    /// Bytes written by `Include`, `Rle` and `Lz` directives, by index of the directive's token.
+    pub includes: HashMap<usize, Vec<u8>>,
+    /// Paths of the files read by include directives, without duplicates.
+    pub files: Vec<String>,
+    /// Fields of the structs declared so far, with their size.
+    pub structs: HashMap<&'a str, Fields<'a>>,
+    /// Size of the variables and of the fields of variables, by name of their constant.
+    pub sizes: HashMap<String, usize>,
+    /// Expression of the address of the current RAM section, and the size reserved in it.
+    section: Option<(&'a TokenRef<'a>, usize)>,
+    /// `#var` directives with the expression of their RAM section and their end in it.
+    vars: Vec<(&'a TokenRef<'a>, &'a TokenRef<'a>, usize)>,
+    /// `#load` directive of the current block, and its location in ROM.
+    load: Option<(&'a TokenRef<'a>, usize)>,
 }
 
 impl<'a> Constants<'a> {
@@ -65,7 +87,9 @@ impl<'a> Constants<'a> {
         None
     }
 
-    fn insert(&mut self, ident: &'a str, const_expr: ConstExpr<'a>) -> Result<(), ()> {
+    fn insert(&mut self, ident: impl Into<Cow<'a, str>>, const_expr: ConstExpr<'a>) -> Result<(), ()> {
+        let ident = ident.into();
+
         for (key, _) in &self.const_exprs {
             if *key == ident {
                 return Err(());
@@ -76,7 +100,7 @@ impl<'a> Constants<'a> {
         Ok(()) 
     }
 
-    fn entries(&self) -> &[(&'a str, ConstExpr<'a>)] {
+    fn entries(&self) -> &[(Cow<'a, str>, ConstExpr<'a>)] {
         &self.const_exprs
     }
 
@@ -88,12 +112,22 @@ impl<'a> Constants<'a> {
         let mut result = Self{ 
             const_exprs: vec![],
             includes: HashMap::new(),
+            files: vec![],
+            structs: HashMap::new(),
+            sizes: HashMap::new(),
+            section: None,
+            vars: vec![],
+            load: None,
         };
 
         result = result.get_constants(ast, &mut fail_safe)?; 
 
         let mut location = 0;
-        result.set_location(op_map, ast, &mut location)?; 
+        result.set_location(op_map, ast, &mut location, &mut 0)?; 
+
+        if let Some((load, _)) = result.load {
+            return Err(err!(ConstantsMsg, BadLoad, load.into()));
+        }
 
         Ok(result)
     }
@@ -115,6 +149,25 @@ impl<'a> Constants<'a> {
             }
         }
 
+        for (key, value) in self.entries() {
+            let result = match value {
+                ConstExpr::Offset{ section, offset } => eval::address(section, *offset, self),
+                ConstExpr::Enum{ name, start, step, index } => eval::member(name, *start, *step, *index, self),
+                _ => continue,
+            };
+
+            match result {
+                Ok(value) => updates.push((key.to_string(), value)),
+                Err(mut e) => errors.append(&mut e),
+            }
+        }
+
+        for (var, section, end) in &self.vars {
+            if let Err(e) = eval::ram_end(var, section, *end, self) {
+                errors.push(e);
+            }
+        }
+
         if errors.is_empty() {
             Ok(updates)
         }else {
@@ -141,8 +194,9 @@ impl<'a> Constants<'a> {
     ) -> Result<Self, AsmErr<'a, ConstantsMsg>> {
         *fail_safe -= 1;
 
-        assert!(*fail_safe != 0, 
-            "Recursion limit reached while reading constants");
+        if *fail_safe == 0 {
+            return Err(bug!(ConstantsMsg, ast.into()));
+        }
 
         for token in ast.children() {
             let err = err!(ConstantsMsg, DuplicateKey, token.into());
@@ -180,15 +234,55 @@ impl<'a> Constants<'a> {
                             self.insert(ident, value).map_err(|_| err)?;
                         }
 
+                        Struct => self.declare_struct(child)?,
+                        Var => self.reserve(child)?,
+                        Enum => self.declare_enum(child)?,
+
+                        DataB|DataW if child.children().is_empty() => {
+                            return Err(err!(ConstantsMsg, BadData, child.into()));
+                        }
+
+                        Fill if data::count(child).is_none() => {
+                            return Err(err!(ConstantsMsg, BadFill, child.into()));
+                        }
+
+                        Align if data::alignment(child).is_none() || data::fill(child).is_none() => {
+                            return Err(err!(ConstantsMsg, BadAlign, child.into()));
+                        }
+
+                        Aligned if data::alignment(child).is_none() || child.children().len() != 1 => {
+                            return Err(err!(ConstantsMsg, BadAlign, child.into()));
+                        }
+
+                        Ram => self.section = Some((child.first(), 0)),
+                        Load => self.declare_load(child)?,
+
                         Include => {
-                            let local = child.first().first().value().as_str().unwrap();
+                            let data = self.read_include(child, child.try_get(0))?;
+                            self.includes.insert(child.index(), data);
+                        }
 
-                            if self.includes.get(local).is_none() {
-                                let data = child.ast().source.main().read_local(local).map_err(|_|
-                                    err!(ConstantsMsg, FileReadFailed, token.into()))?;
+                        Rle|Lz => {
+                            let ident = match child.try_get(0) {
+                                Some(ident) if ident.ty() == Identifier => ident.value().as_str().unwrap(),
+                                Some(other) => return Err(bug!(ConstantsMsg, other.into())),
+                                None => return Err(err!(ConstantsMsg, NoFilePath, child.into())),
+                            };
 
-                                self.includes.insert(local, data);
+                            let data = self.read_include(child, child.try_get(1))?;
+
+                            let packed = match child.ty() {
+                                Rle => compress::rle(&data),
+                                _ => compress::lz(&data),
+                            };
+
+                            if packed.len() > u16::MAX as usize {
+                                return Err(err!(ConstantsMsg, CompressedTooLarge, token.into()));
                             }
+
+                            let value = ConstExpr::Value(Value::U16(packed.len() as u16));
+                            self.insert(ident, value).map_err(|_| err)?;
+                            self.includes.insert(child.index(), packed);
                         }
 
                         _ => {}
@@ -202,58 +296,307 @@ impl<'a> Constants<'a> {
         Ok(self)
     }
 
+    /// Declares the offset of each field, as `STRUCT_FIELD`, and the total size, as `STRUCT_SIZE`.
+    fn declare_struct(&mut self, directive: &'a TokenRef<'a>) -> Result<(), AsmErr<'a, ConstantsMsg>> {
+        let children = directive.children();
+        let name = children.first()
+            .filter(|name| name.ty() == Identifier)
+            .map(|name| name.value().as_str().unwrap())
+            .ok_or_else(|| err!(ConstantsMsg, BadField, directive.into()))?;
+
+        let mut fields = vec![];
+        let mut offset = 0;
+
+        for pair in children[1..].chunks(2) {
+            let [field, size_token] = pair else {
+                return Err(err!(ConstantsMsg, BadField, pair[0].into()));
+            };
+
+            if field.ty() != Identifier {
+                return Err(err!(ConstantsMsg, BadField, (*field).into()));
+            }
+
+            let field_name = field.value().as_str().unwrap();
+            let (size, _) = self.size_of_var(size_token)?;
+
+            if size == 0 {
+                return Err(err!(ConstantsMsg, BadField, (*size_token).into()));
+            }
+
+            self.insert(format!("{name}_{field_name}"), ConstExpr::Value(Value::U16(offset as u16)))
+                .map_err(|_| err!(ConstantsMsg, DuplicateKey, (*field).into()))?;
+
+            fields.push((field_name, size));
+            offset += size;
+        }
+
+        self.insert(format!("{name}_SIZE"), ConstExpr::Value(Value::U16(offset as u16)))
+            .map_err(|_| err!(ConstantsMsg, DuplicateKey, children[0].into()))?;
+
+        self.structs.insert(name, fields);
+        Ok(())
+    }
+
+    /// Reserves a variable or an array of variables in the current RAM section.
+    /// Each field of a struct gets a constant as `VAR_FIELD`, for the first element of an array.
+    fn reserve(&mut self, directive: &'a TokenRef<'a>) -> Result<(), AsmErr<'a, ConstantsMsg>> {
+        let children = directive.children();
+        let name = children.first()
+            .filter(|name| name.ty() == Identifier)
+            .map(|name| name.value().as_str().unwrap())
+            .ok_or_else(|| err!(ConstantsMsg, BadVar, directive.into()))?;
+
+        let (section, reserved) = self.section.ok_or_else(|| err!(ConstantsMsg, NoSection, directive.into()))?;
+
+        let (size, fields) = match children.get(1) {
+            Some(size) => self.size_of_var(size)?,
+            None => return Err(err!(ConstantsMsg, BadVar, directive.into())),
+        };
+
+        let count = match &children[2..] {
+            [] => 1,
+            [count] if count.ty() == Lit => count.first().value().as_num()
+                .map_err(|_| err!(ConstantsMsg, BadVar, (*count).into()))?,
+            _ => return Err(err!(ConstantsMsg, BadVar, directive.into())),
+        };
+
+        if size * count == 0 {
+            return Err(err!(ConstantsMsg, BadVar, directive.into()));
+        }
+
+        let duplicate = |_| err!(ConstantsMsg, DuplicateKey, children[0].into());
+        self.insert(name, ConstExpr::Offset{ section, offset: reserved }).map_err(duplicate)?;
+
+        match fields {
+            None => { self.sizes.insert(name.into(), size); }
+
+            Some(fields) => {
+                let mut offset = reserved;
+
+                for (field, field_size) in fields {
+                    let key = format!("{name}_{field}");
+                    self.sizes.insert(key.clone(), field_size);
+                    self.insert(key, ConstExpr::Offset{ section, offset }).map_err(duplicate)?;
+                    offset += field_size;
+                }
+            }
+        }
+
+        self.section = Some((section, reserved + size * count));
+        self.vars.push((directive, section, reserved + size * count));
+        Ok(())
+    }
+
+    /// Declares the names of an enum, after their prefix, and the number of names as `PREFIXCOUNT` with a prefix.
+    fn declare_enum(&mut self, directive: &'a TokenRef<'a>) -> Result<(), AsmErr<'a, ConstantsMsg>> {
+        let mut children = directive.children().into_iter().peekable();
+
+        let prefix = children
+            .next_if(|child| child.ty() == Lit && child.first().ty() == LitStr)
+            .map(|lit| lit.first().value().as_str().unwrap());
+
+        let mut values = vec![];
+
+        while let Some(value) = children.next_if(|child| matches!(child.ty(), Lit|At)) {
+            values.push(value);
+        }
+
+        let (start, step) = match values[..] {
+            [] => (None, None),
+            [start] => (Some(start), None),
+            [start, step] => (Some(start), Some(step)),
+            _ => return Err(err!(ConstantsMsg, BadEnum, values[2].into())),
+        };
+
+        let names = children.collect::<Vec<_>>();
+
+        if names.is_empty() {
+            return Err(err!(ConstantsMsg, BadEnum, directive.into()));
+        }
+
+        for (index, name) in names.iter().enumerate() {
+            if name.ty() != Identifier {
+                return Err(err!(ConstantsMsg, BadEnum, (*name).into()));
+            }
+
+            let key = format!("{}{}", prefix.unwrap_or_default(), name.value().as_str().unwrap());
+
+            self.insert(key, ConstExpr::Enum{ name, start, step, index })
+                .map_err(|_| err!(ConstantsMsg, DuplicateKey, (*name).into()))?;
+        }
+
+        if let Some(prefix) = prefix {
+            let count = u8::try_from(names.len()).map_or(Value::U16(names.len() as u16), Value::U8);
+
+            self.insert(format!("{prefix}COUNT"), ConstExpr::Value(count))
+                .map_err(|_| err!(ConstantsMsg, DuplicateKey, directive.into()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Declares the address of a block in RAM as `NAME_RAM`, its location in ROM as `NAME_ROM` and its size as `NAME_SIZE`.
+    /// The location and the size are known once the instructions before and in the block are placed.
+    fn declare_load(&mut self, directive: &'a TokenRef<'a>) -> Result<(), AsmErr<'a, ConstantsMsg>> {
+        let [name, address] = directive.children()[..] else {
+            return Err(err!(ConstantsMsg, BadLoad, directive.into()));
+        };
+
+        let name = name.value().as_str().unwrap();
+        let duplicate = |_| err!(ConstantsMsg, DuplicateKey, directive.into());
+
+        self.insert(format!("{name}_RAM"), ConstExpr::Offset{ section: address, offset: 0 }).map_err(duplicate)?;
+        self.insert(format!("{name}_ROM"), ConstExpr::Mark).map_err(duplicate)?;
+        self.insert(format!("{name}_SIZE"), ConstExpr::Mark).map_err(duplicate)
+    }
+
+    /// Size in bytes given by a literal, or by the name of a struct with its fields.
+    fn size_of_var(
+        &self,
+        token: &'a TokenRef<'a>,
+    ) -> Result<(usize, Option<Fields<'a>>), AsmErr<'a, ConstantsMsg>> {
+        match token.ty() {
+            Lit => token.first().value().as_num().map(|size| (size, None))
+                .map_err(|_| err!(ConstantsMsg, BadField, token.into())),
+
+            Identifier => {
+                let name = token.value().as_str().unwrap();
+                let fields = self.structs.get(name).ok_or_else(|| err!(ConstantsMsg, NoStruct, token.into()))?;
+                Ok((fields.iter().map(|(_, size)| size).sum(), Some(fields.clone())))
+            }
+
+            _ => Err(err!(ConstantsMsg, BadField, token.into())),
+        }
+    }
+
+    /// Reads an included file.
+    /// `lit` is the token holding the path, relative to the file of the directive.
+    fn read_include(
+        &mut self,
+        directive: &'a TokenRef<'a>,
+        lit: Option<&'a TokenRef<'a>>,
+    ) -> Result<Vec<u8>, AsmErr<'a, ConstantsMsg>> {
+        let local = lit.and_then(|lit| lit.try_get(0))
+            .filter(|lit| lit.ty() == LitStr)
+            .map(|lit| lit.value().as_str().unwrap())
+            .ok_or_else(|| err!(ConstantsMsg, NoFilePath, directive.into()))?;
+
+        let source = directive.ast().source;
+        let input = source.get(directive.file()).unwrap_or_else(|| source.main());
+
+        let (path, data) = source.read(input, local).map_err(|_|
+            err!(ConstantsMsg, FileReadFailed, directive.into()))?;
+
+        if !self.files.contains(&path) {
+            self.files.push(path);
+        }
+
+        Ok(data)
+    }
+
+    /// Bytes written by an `Include`, `Rle` or `Lz` directive.
+    pub fn included(&self, directive: &TokenRef<'a>) -> Option<&[u8]> {
+        self.includes.get(&directive.index()).map(Vec::as_slice)
+    }
+
     /// Increases the current location by the size in bytes of a token.
+    /// `block` is the address of the last marker, where the block of code being sized starts.
     fn set_location(
         &mut self,
         op_map: &OpMap,
         root: &'a TokenRef<'a>,
         location: &mut usize,
+        block: &mut usize,
     ) -> Result<(), AsmErr<'a, ConstantsMsg>> {
         for token in root.children() {
             match token.ty() {
                 MacroCall => {
                     for child in token.children() {
                         if child.ty() == MacroBody {
-                            self.set_location(op_map, child, location)?;
+                            self.set_location(op_map, child, location, block)?;
                             break;
                         }
                     }
                 }
 
                 Instruction => *location += op_map.get(token).len as usize,
-                Lit => *location += Self::size_of_lit(token),
+                Lit => *location += Self::size_of_lit(token).ok_or_else(|| bug!(ConstantsMsg, token.into()))?,
 
+                // Undeclared identifiers are reported when validating the constants.
                 Identifier => {
                     let ident = token.value().as_str().unwrap();
-                    *location += self.size_of_ident(ident);
+
+                    if self.get(ident).is_some() {
+                        *location += self.size_of_ident(ident).ok_or_else(|| bug!(ConstantsMsg, token.into()))?;
+                    }
                 }
 
+                // Labels of a `#load` block are addresses in RAM.
                 Label => {
-                    let value = ConstExpr::Value(Value::U16(*location as u16));
+                    let value = match self.load {
+                        Some((load, start)) => ConstExpr::Offset{ section: load.get(1), offset: *location - start },
+                        None => ConstExpr::Value(Value::U16(*location as u16)),
+                    };
+
                     let key = token.value().as_str().unwrap();
                     *self.get_mut(key).unwrap() = value;
                 }
 
-                Marker => self.set_location(op_map, token, location)?,
+                Marker => self.set_location(op_map, token, location, block)?,
 
                 AnonMark|NamedMark => {
+                    if let Some((load, _)) = self.load {
+                        return Err(err!(ConstantsMsg, BadLoad, load.into()));
+                    }
+
                     let marker_location = token.first().first().value().as_num().unwrap();
 
-                    if *location <= marker_location {
-                        *location = marker_location;
+                    if *location > marker_location {
+                        let overlap = Overlap{ block: *block, bytes: *location - marker_location };
+                        return Err(err!(ConstantsMsg, overlap, token.into()));
                     }
 
-                    else {
-                        return Err(err!(ConstantsMsg, MisplacedMarker, token.into())); 
-                    }
+                    *location = marker_location;
+                    *block = marker_location;
                 }
 
                 Directive => {
                     let dir = token.first();
                     
-                    if dir.ty() == Include {
-                        let path = dir.first().first().value().as_str().unwrap();
-                        *location += self.includes.get(path).unwrap().len();
+                    match dir.ty() {
+                        Include|Rle|Lz => {
+                            *location += self.included(dir).ok_or_else(|| bug!(ConstantsMsg, dir.into()))?.len();
+                        }
+
+                        DataB|DataW|Fill => *location += data::size(dir).ok_or_else(|| bug!(ConstantsMsg, dir.into()))?,
+                        Align => *location += data::padding(dir, *location).ok_or_else(|| bug!(ConstantsMsg, dir.into()))?,
+
+                        Aligned => {
+                            let alignment = data::alignment(dir).ok_or_else(|| bug!(ConstantsMsg, dir.into()))?;
+
+                            let bytes = *location % alignment;
+
+                            if bytes != 0 {
+                                let misaligned = Misaligned{ alignment, bytes };
+                                return Err(err!(ConstantsMsg, misaligned, dir.into()));
+                            }
+                        }
+
+                        Load => {
+                            if self.load.is_some() {
+                                return Err(err!(ConstantsMsg, BadLoad, dir.into()));
+                            }
+
+                            self.load = Some((dir, *location));
+                            self.set_block(dir, "ROM", *location);
+                        }
+
+                        EndLoad => {
+                            let (load, start) = self.load.take().ok_or_else(|| err!(ConstantsMsg, BadLoad, dir.into()))?;
+                            self.set_block(load, "SIZE", *location - start);
+                        }
+
+                        _ => {}
                     }
                 }
 
@@ -264,35 +607,42 @@ impl<'a> Constants<'a> {
         Ok(())
     }
     
-    fn size_of_ident(&self, ident: &'a str) -> usize {
-        match self.get(ident).unwrap() {
+    /// Sets the location or the size of a `#load` block.
+    fn set_block(&mut self, load: &'a TokenRef<'a>, suffix: &str, value: usize) {
+        let key = format!("{}_{suffix}", load.first().value().as_str().unwrap());
+        *self.get_mut(&key).unwrap() = ConstExpr::Value(Value::U16(value as u16));
+    }
+
+    fn size_of_ident(&self, ident: &'a str) -> Option<usize> {
+        match self.get(ident)? {
             ConstExpr::Value(value) => {
                 match value {
-                    Value::U8(_) => 1,
-                    Value::U16(_) => 2,
-                    Value::Str(v) => v.len(),
-                    _ => bug!("Unhandled `Value` type.")
+                    Value::U8(_) => Some(1),
+                    Value::U16(_) => Some(2),
+                    Value::Str(v) => Some(v.len()),
+                    _ => None,
                 }
             }
 
-            ConstExpr::Mark => 2,
+            ConstExpr::Mark|ConstExpr::Offset{..} => Some(2),
+            ConstExpr::Enum{..} => Some(1),
 
             ConstExpr::Expr(token) => {
                 match token.parent().ty() {
-                    DefB => 1,
-                    DefW => 2,
-                    _ => bug!("Unexpected token type")
+                    DefB => Some(1),
+                    DefW => Some(2),
+                    _ => None,
                 }
             }
         }
     }
 
-    fn size_of_lit(lit: &TokenRef<'a>) -> usize {
+    fn size_of_lit(lit: &TokenRef<'a>) -> Option<usize> {
         match lit.first().value() {
-            Value::U8(_) => 1,    
-            Value::U16(_) => 2,    
-            Value::Str(v) => v.len(),    
-            _ => bug!("Unhandled literal type."),
+            Value::U8(_) => Some(1),
+            Value::U16(_) => Some(2),
+            Value::Str(v) => Some(v.len()),
+            _ => None,
         }
     }
 
@@ -304,7 +654,7 @@ impl<'a> Constants<'a> {
         assert_eq!(ast_ref.ty(), Root);
 
         let mut errors = vec![];
-        // Check for undeclared identifiers.
+        // Check for undeclared identifiers, and variables accessed with the wrong width.
         self.validate_walk(ast_ref, &mut errors);
 
         if errors.is_empty() {
@@ -320,18 +670,73 @@ impl<'a> Constants<'a> {
         errors: &mut Vec<AsmErr<'a, ConstantsValidationMsg>>
     ) {
         for child in scope.children() {
-            if child.ty() == Identifier {
-                let ident = child.value().as_str().unwrap();
+            match child.ty() {
+                // Names of fields and structs are not constants.
+                Struct|Var => continue,
 
-                if self.get(ident).is_none() {
-                    errors.push(err!(ConstantsValidationMsg, IdentNotFound, child.into())); 
+                // Only the start and the step of an enum use constants.
+                Enum => {
+                    child.children().into_iter()
+                        .filter(|value| value.ty() == At)
+                        .for_each(|at| self.validate_walk(at, errors));
+
+                    continue;
+                }
+
+                // The name of a block is not a constant.
+                Load => {
+                    child.children().into_iter()
+                        .filter(|expr| expr.ty() == Expr)
+                        .for_each(|expr| self.validate_walk(expr, errors));
+
+                    continue;
+                }
+
+                Identifier => {
+                    let ident = child.value().as_str().unwrap();
+
+                    if self.get(ident).is_none() {
+                        errors.push(err!(ConstantsValidationMsg, IdentNotFound, child.into())); 
+                    }
                 }
+
+                Instruction => self.validate_width(child, errors),
+                _ => {}
             }
 
             self.validate_walk(child, errors);
         }
     }
 
+    /// A variable of 1 or 2 bytes loaded or stored at its address must be accessed with the same width.
+    /// An expression such as `(VAR + 1)` reads a single byte of a word.
+    fn validate_width(
+        &self,
+        instruction: &TokenRef<'a>,
+        errors: &mut Vec<AsmErr<'a, ConstantsValidationMsg>>
+    ) {
+        if !matches!(instruction.try_first_of(InstrName).map(|name| name.first().ty()), Some(Ld|Ldh)) {
+            return;
+        }
+
+        let args = instruction.children().into_iter()
+            .filter(|child| child.ty() == Argument)
+            .map(|arg| arg.first())
+            .collect::<Vec<_>>();
+
+        let Some(ident) = args.iter()
+            .filter(|arg| arg.ty() == At && arg.children().len() == 1)
+            .map(|at| at.first())
+            .find(|ident| ident.ty() == Identifier) else { return };
+
+        let width = if args.iter().any(|arg| arg.ty() == Register && arg.first().ty() == Sp) { 2 }else{ 1 };
+        let size = ident.value().as_str().ok().and_then(|name| self.sizes.get(name));
+
+        if let Some(&size) = size.filter(|size| **size <= 2 && **size != width) {
+            errors.push(err!(ConstantsValidationMsg, FieldWidth{ size, width }, ident.into()));
+        }
+    }
+
     #[cfg(debug_assertions)]
     pub fn debug(&self) {
         title("Constant values");
@@ -344,32 +749,16 @@ impl<'a> Constants<'a> {
                 continue;
             }
 
-            let value_str;
-            let ty_str;
-
-            if let ConstExpr::Value(v) = value {
-                match v {
-                    Value::U8(v) => {
-                        value_str = v.to_string();
-                        ty_str = "BYTE";
-                    }
-                    Value::U16(v) => {
-                        value_str = v.to_string();
-                        ty_str = "WORD";
-                    }
-                    Value::Str(v) => {
-                        value_str = (*v).to_string();
-                        ty_str = "STRG";
-                    }
-                    _ => bug!("Unexpected Value type")
-                }
-            }else {
-                bug!("Unexpected ConstExpr type");
+            let (ty_str, value_str) = match value {
+                ConstExpr::Value(Value::U8(v)) => ("BYTE", v.to_string()),
+                ConstExpr::Value(Value::U16(v)) => ("WORD", v.to_string()),
+                ConstExpr::Value(Value::Str(v)) => ("STRG", (*v).to_string()),
+                _ => continue,
             };
 
             let len = 48usize.saturating_sub(key.len());
             let bar = "─".repeat(len);
-            let hex_str = format!("{:X}", value_str.parse::<usize>().unwrap());
+            let hex_str = value_str.parse::<usize>().map_or_else(|_| String::new(), |v| format!("{v:X}"));
             let len2 = 5usize.saturating_sub(hex_str.len());
             let bar2 = "─".repeat(len2);
 
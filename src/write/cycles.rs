@@ -0,0 +1,143 @@
+use crate::{
+    write::ops::OpMap,
+    token::read::TokenRef,
+    parse::lex::TokenType::*,
+    error::asm::{ AsmErr, CyclesMsg::{self, *} },
+};
+
+/// Machine cycles of a sequence of instructions,
+/// when all the conditions are met and when none are.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Cycles {
+    pub taken: usize,
+    pub skipped: usize,
+}
+
+impl From<(u8, u8)> for Cycles {
+    fn from((taken, skipped): (u8, u8)) -> Self {
+        Self{ taken: taken as usize, skipped: skipped as usize }
+    }
+}
+
+impl std::ops::AddAssign<(u8, u8)> for Cycles {
+    fn add_assign(&mut self, (taken, skipped): (u8, u8)) {
+        self.taken += taken as usize;
+        self.skipped += skipped as usize;
+    }
+}
+
+impl std::fmt::Display for Cycles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.taken == self.skipped {
+            write!(f, "{}", self.taken)
+        }else {
+            write!(f, "{}/{}", self.taken, self.skipped)
+        }
+    }
+}
+
+/// Tokens in the order they are written, with the bodies of macro calls in place of the calls.
+pub fn flatten<'a>(root: &'a TokenRef<'a>, tokens: &mut Vec<&'a TokenRef<'a>>) {
+    for token in root.children() {
+        if token.ty() == MacroCall {
+            token.try_first_of(MacroBody).into_iter().for_each(|body| flatten(body, tokens));
+        }else {
+            tokens.push(token);
+        }
+    }
+}
+
+/// Checks that the instructions after each `#cycles` directive fit in its budget,
+/// when all the conditions are met.
+/// The block ends at the next marker or `#cycles` directive.
+pub fn check<'a>(root: &'a TokenRef<'a>, op_map: &OpMap) -> Result<(), Vec<AsmErr<'a, CyclesMsg>>> {
+    let mut tokens = vec![];
+    flatten(root, &mut tokens);
+
+    let mut errors = vec![];
+    // Directive of the current block, and the cycles so far.
+    let mut block: Option<(&TokenRef, Cycles)> = None;
+
+    for token in tokens {
+        match token.ty() {
+            Instruction => {
+                if let Some((_, cycles)) = &mut block {
+                    *cycles += op_map.get(token).cycles;
+                }
+            }
+
+            Marker => end_block(block.take(), &mut errors),
+
+            Directive if token.first().ty() == Cycles => {
+                end_block(block.take(), &mut errors);
+                block = Some((token.first(), Cycles::default()));
+            }
+
+            _ => {}
+        }
+    }
+
+    end_block(block, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    }else {
+        Err(errors)
+    }
+}
+
+fn end_block<'a>(block: Option<(&'a TokenRef<'a>, Cycles)>, errors: &mut Vec<AsmErr<'a, CyclesMsg>>) {
+    let Some((directive, cycles)) = block else {
+        return;
+    };
+
+    let budget = directive.try_get(0)
+        .filter(|lit| lit.ty() == Lit)
+        .and_then(|lit| lit.first().value().as_num().ok());
+
+    match budget {
+        None => errors.push(err!(CyclesMsg, NoBudget, directive.into())),
+        Some(budget) if cycles.taken > budget => errors.push(err!(CyclesMsg, OverBudget, directive.into())),
+        _ => {}
+    }
+}
+
+/// Cycles of the instructions from a label to another, in the order they are written.
+/// Returns the name of the label that was not found, the end must come after the start.
+pub fn between<'a, 'b>(
+    root: &'a TokenRef<'a>,
+    op_map: &OpMap,
+    start: &'b str,
+    end: &'b str,
+) -> Result<Cycles, &'b str> {
+    let mut tokens = vec![];
+    flatten(root, &mut tokens);
+
+    let mut cycles = None;
+
+    for token in tokens {
+        match token.ty() {
+            Marker => {
+                let name = token.first().value().as_str().ok();
+
+                if cycles.is_some() && name == Some(end) {
+                    return cycles.ok_or(end);
+                }
+
+                if cycles.is_none() && name == Some(start) {
+                    cycles = Some(Cycles::default());
+                }
+            }
+
+            Instruction => {
+                if let Some(cycles) = &mut cycles {
+                    *cycles += op_map.get(token).cycles;
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    Err(if cycles.is_some() { end }else{ start })
+}
@@ -0,0 +1,112 @@
+use crate::{
+    write::constants::Constants,
+    token::{ read::TokenRef, expr::eval },
+    parse::lex::TokenType::*,
+    error::asm::{ AsmErr, ExprMsg },
+};
+
+/// Bytes of each value: 2 for `#word`, 1 for `#byte` and `#fill`.
+fn width(directive: &TokenRef) -> usize {
+    if directive.ty() == DataW { 2 }else{ 1 }
+}
+
+/// Number of copies of a `#fill`, a number before its value.
+pub fn count(directive: &TokenRef) -> Option<usize> {
+    match &directive.children()[..] {
+        [count, _] if count.ty() == Lit => count.first().value().as_num().ok(),
+        _ => None,
+    }
+}
+
+/// Power of 2 of an `#align` or an `#aligned`, as a number of bytes.
+/// Alignments are at most of 64KB.
+pub fn alignment(directive: &TokenRef) -> Option<usize> {
+    let power = directive.try_get(0).filter(|power| power.ty() == Lit)?.first().value().as_num().ok()?;
+    (power <= 16).then(|| 1 << power)
+}
+
+/// Byte of an `#align`, `&FF` by default as before markers.
+pub fn fill(directive: &TokenRef) -> Option<u8> {
+    match &directive.children()[..] {
+        [_] => Some(0xFF),
+        [_, fill] if fill.ty() == Lit => fill.first().value().as_num().ok().and_then(|fill| u8::try_from(fill).ok()),
+        _ => None,
+    }
+}
+
+/// Bytes from `location` to the next multiple of the alignment.
+pub fn padding(directive: &TokenRef, location: usize) -> Option<usize> {
+    alignment(directive).map(|alignment| (alignment - location % alignment) % alignment)
+}
+
+/// Size of the data of a `#byte`, `#word` or `#fill`, known before the constants have a value.
+/// A string takes one value per character.
+pub fn size(directive: &TokenRef) -> Option<usize> {
+    if directive.ty() == Fill {
+        return count(directive);
+    }
+
+    let values = directive.children().iter().map(|item| match item.ty() {
+        Lit => item.first().value().as_str().map_or(1, str::len),
+        _ => 1,
+    }).sum::<usize>();
+
+    Some(values * width(directive))
+}
+
+/// Writes the data of a `#byte`, `#word` or `#fill`, once all the constants have a value.
+/// Words are little-endian, negative values are written as two's complement.
+pub fn bytes<'a>(
+    directive: &'a TokenRef<'a>,
+    constants: &'a Constants<'a>,
+) -> Result<Vec<u8>, Vec<AsmErr<'a, ExprMsg>>> {
+    let width = width(directive);
+    let children = directive.children();
+
+    let items = match directive.ty() {
+        Fill => children.get(1..).unwrap_or_default(),
+        _ => &children[..],
+    };
+
+    let mut bytes = vec![];
+    let mut errors = vec![];
+
+    for item in items {
+        if item.ty() == Lit && item.first().ty() == LitStr {
+            for c in item.first().value().as_str().unwrap().bytes() {
+                bytes.extend_from_slice(&(c as u16).to_le_bytes()[..width]);
+            }
+
+            continue;
+        }
+
+        let value = match eval::value(item, constants) {
+            Ok(value) => value,
+
+            Err(mut e) => {
+                errors.append(&mut e);
+                continue;
+            }
+        };
+
+        let (min, max) = if width == 2 { (i16::MIN as isize, u16::MAX as isize) }else{ (i8::MIN as isize, u8::MAX as isize) };
+
+        if value < min || value > max {
+            errors.push(err!(ExprMsg, ExprMsg::DataOverflow, (*item).into()));
+            continue;
+        }
+
+        bytes.extend_from_slice(&(value as u16).to_le_bytes()[..width]);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    if directive.ty() == Fill {
+        let count = count(directive).ok_or_else(|| vec![bug!(ExprMsg, directive.into())])?;
+        bytes = bytes.repeat(count);
+    }
+
+    Ok(bytes)
+}
@@ -0,0 +1,47 @@
+use crate::{
+    parse::{ source::Source, library },
+    write::constants::Constants,
+    error::asm::EncodeErr,
+};
+
+/// Writes a makefile rule with the output as target and every file it was built from.
+/// Files of the standard library are part of the assembler and are not listed.
+pub fn build(
+    path: &str,
+    target: &str,
+    source: &Source,
+    constants: &Constants,
+    base: Option<&str>,
+) -> Result<(), EncodeErr> {
+    let files = source.inputs.iter()
+        .map(|input| input.path.as_str())
+        .chain(constants.files.iter().map(String::as_str))
+        .chain(base)
+        .filter(|file| !library::is_library(file))
+        .collect::<Vec<_>>();
+
+    std::fs::write(path, rule(target, &files)).map_err(|_| EncodeErr::DepsFailed)
+}
+
+/// Each dependency but the main file also gets an empty rule,
+/// so make does not fail when one is removed.
+fn rule(target: &str, files: &[&str]) -> String {
+    let mut rule = format!("{}:", escape(target));
+
+    for file in files {
+        rule.push_str(" \\\n  ");
+        rule.push_str(&escape(file));
+    }
+
+    rule.push('\n');
+
+    for file in files.iter().skip(1) {
+        rule.push_str(&format!("\n{}:\n", escape(file)));
+    }
+
+    rule
+}
+
+fn escape(path: &str) -> String {
+    path.replace(' ', "\\ ").replace('$', "$$").replace('#', "\\#")
+}
@@ -1,6 +1,6 @@
 
 use crate::{
-    write::{ ops::OpMap, constants::Constants },
+    write::{ ops::OpMap, constants::Constants, patch::{ self, Block }, data },
     token::read::TokenRef,
     parse::lex::TokenType::*,
     error::asm::EncodeErr,
@@ -27,30 +27,153 @@ pub fn u16_to_bytes(u: u16) -> Vec<u8> {
     u.to_le_bytes().to_vec()
 }
 
-pub fn build(
-    path: &str,
-    ast: &TokenRef, 
+/// Layout of the output file.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Cartridge image.
+    #[default]
+    Rom,
+    /// Intel HEX records, for flash cartridge tools.
+    Hex,
+    /// Raw bytes, such as a segment loaded by a program.
+    Bin,
+    /// IPS patch of the base ROM.
+    Ips,
+    /// BPS patch of the base ROM.
+    Bps,
+}
+
+impl Format {
+
+    /// Format from its name on the command line.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gb" => Some(Self::Rom),
+            "hex" => Some(Self::Hex),
+            "bin" => Some(Self::Bin),
+            "ips" => Some(Self::Ips),
+            "bps" => Some(Self::Bps),
+            _ => None,
+        }
+    }
+
+    /// Does the output hold a whole cartridge image, with a header to patch?
+    pub const fn cartridge(self, range: Option<(usize, usize)>) -> bool {
+        matches!((self, range), (Self::Rom|Self::Ips|Self::Bps, _)|(Self::Hex, None))
+    }
+
+    /// Is the output a patch, that needs a base ROM?
+    pub const fn patch(self) -> bool {
+        matches!(self, Self::Ips|Self::Bps)
+    }
+
+}
+
+/// Space filled to reach a marker, from `start` to the address of the marker.
+#[derive(Debug, Copy, Clone)]
+pub struct Gap<'a> {
+    pub marker: &'a TokenRef<'a>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Encodes the image, and patches the checksums of the header of a cartridge.
+/// With a `base` ROM, the gaps before markers keep its bytes instead of being filled with `0xFF`.
+/// Returns the image with the blocks written by the source.
+pub fn build<'a>(
+    ast: &'a TokenRef<'a>,
     op_map: &OpMap, 
-    constants: &Constants
-) -> Result<(), EncodeErr> {
+    constants: &'a Constants<'a>,
+    cartridge: bool,
+    base: Option<&[u8]>,
+) -> Result<(Vec<u8>, Vec<Block<'a>>), EncodeErr> {
     let mut bytes = vec![];
+    let mut gaps = vec![];
 
-    encode(ast, op_map, constants, &mut bytes)?;
-    patch_checksum(&mut bytes)?;
-    write(&bytes, path)?;
+    encode(ast, op_map, constants, &mut bytes, &mut gaps)?;
 
-    Ok(())
+    let blocks = patch::blocks(&gaps, bytes.len());
+
+    if let Some(base) = base {
+        bytes = patch::over(base, &bytes, &blocks);
+    }
+
+    if cartridge {
+        patch_checksum(&mut bytes)?;
+    }
+
+    Ok((bytes, blocks))
+}
+
+/// Content of the output file, from the addresses in `range` of the image or the whole image.
+/// The end of the range is excluded.
+/// Patches are made against `base`, or an empty ROM.
+pub fn output(
+    bytes: &[u8],
+    format: Format,
+    range: Option<(usize, usize)>,
+    base: Option<&[u8]>,
+) -> Result<Vec<u8>, EncodeErr> {
+    let (start, end) = range.unwrap_or((0, bytes.len()));
+    let segment = bytes.get(start..end).ok_or(EncodeErr::BadRange)?;
+    let base = base.unwrap_or_default();
+
+    match format {
+        Format::Rom|Format::Bin => Ok(segment.to_vec()),
+        Format::Hex => Ok(intel_hex(segment, start).into_bytes()),
+        Format::Ips => patch::ips(base, segment),
+        Format::Bps => Ok(patch::bps(base, segment)),
+    }
 }
 
-pub fn encode(
-    ast: &TokenRef, 
+/// Data records of at most 16 bytes, from `address`.
+/// An extended linear address record starts each block of 64KB after the first one.
+fn intel_hex(bytes: &[u8], address: usize) -> String {
+    let mut text = String::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let at = address + offset;
+
+        if at > 0xFFFF && (at & 0xFFFF == 0 || offset == 0) {
+            text.push_str(&hex_record(0x04, 0, &((at >> 16) as u16).to_be_bytes()));
+        }
+
+        let len = 16.min(bytes.len() - offset).min(0x10000 - (at & 0xFFFF));
+        text.push_str(&hex_record(0x00, at as u16, &bytes[offset..offset + len]));
+        offset += len;
+    }
+
+    text.push_str(&hex_record(0x01, 0, &[]));
+    text
+}
+
+fn hex_record(ty: u8, address: u16, data: &[u8]) -> String {
+    let [high, low] = address.to_be_bytes();
+    let mut record = vec![data.len() as u8, high, low, ty];
+    record.extend_from_slice(data);
+
+    let checksum = record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+    record.push(checksum);
+
+    format!(":{}\n", record.iter().map(|byte| format!("{byte:02X}")).collect::<String>())
+}
+
+/// Appends the bytes of the source to `bytes`, and the space filled to reach markers to `gaps`.
+pub fn encode<'a>(
+    ast: &'a TokenRef<'a>,
     op_map: &OpMap, 
-    constants: &Constants,
+    constants: &'a Constants<'a>,
     bytes: &mut Vec<u8>,
+    gaps: &mut Vec<Gap<'a>>,
 ) -> Result<(), EncodeErr> {
     for child in ast.children() {
         match child.ty() {
-            MacroCall => encode(child.first_of(MacroBody), op_map, constants, bytes)?,
+            MacroCall => {
+                let body = child.try_first_of(MacroBody).ok_or(EncodeErr::Internal(source!()))?;
+                encode(body, op_map, constants, bytes, gaps)?;
+            }
+
             Instruction => encode_instruction(child, op_map, constants, bytes),
 
             // Fill empty space to reach markers.
@@ -60,12 +183,14 @@ pub fn encode(
                 let location = match marker_kind.ty() {
                     NamedMark | AnonMark => marker_kind.leaf().value().as_num().unwrap(),
                     Label => continue,
-                    _ => bug!("Invalid Marker type."),
+                    _ => return Err(EncodeErr::Internal(source!())),
                 };
 
-                let diff = location - bytes.len();
+                // Blocks running past a marker are reported with the constants.
+                let diff = location.checked_sub(bytes.len()).ok_or(EncodeErr::Internal(source!()))?;
                 let mut fill = vec![255u8; diff];
 
+                gaps.push(Gap{ marker: child, start: bytes.len(), end: location });
                 bytes.append(&mut fill);
             }
 
@@ -81,17 +206,19 @@ pub fn encode(
                 bytes.append(&mut b);
             }
 
-            Directive => {
-                if child.first().ty() == Include {
-                    let path = child.leaf().value().as_str().unwrap();
+            Directive if matches!(child.first().ty(), Include|Rle|Lz) => {
+                bytes.extend_from_slice(constants.included(child.first()).ok_or(EncodeErr::Internal(source!()))?);
+            }
 
-                    constants.includes.get(path).map_or_else(|| {
-                        bug!("Could not include file."); 
-                    }, |b| {
-                        let mut b = b.clone();
-                        bytes.append(&mut b);
-                    });
-                }
+            // Values that do not fit are reported with the checks.
+            Directive if matches!(child.first().ty(), DataB|DataW|Fill) => {
+                bytes.append(&mut data::bytes(child.first(), constants).map_err(|_| EncodeErr::Internal(source!()))?);
+            }
+
+            Directive if child.first().ty() == Align => {
+                let padding = data::padding(child.first(), bytes.len()).ok_or(EncodeErr::Internal(source!()))?;
+                let fill = data::fill(child.first()).ok_or(EncodeErr::Internal(source!()))?;
+                bytes.resize(bytes.len() + padding, fill);
             }
 
             _ => {
@@ -159,14 +286,14 @@ fn encode_instruction(
 
 /// Patches header and ROM checksums into the binary.
 fn patch_checksum(bytes: &mut [u8]) -> Result<(), EncodeErr> {
-    if bytes.len() < 0x014F {
+    if bytes.len() < 0x0150 {
         return Err(EncodeErr::BadHeader);
     }
 
     let mut x = 0u8;
 
-    for i in 0x0134..=0x014C {
-        x = x.wrapping_sub(bytes[i]).wrapping_sub(1);
+    for byte in &bytes[0x0134..=0x014C] {
+        x = x.wrapping_sub(*byte).wrapping_sub(1);
     }
 
     bytes[0x014D] = x;
@@ -184,7 +311,7 @@ fn patch_checksum(bytes: &mut [u8]) -> Result<(), EncodeErr> {
     Ok(())
 }
 
-fn write(bytes: &[u8], path: &str) -> Result<(), EncodeErr> {
+pub fn write(bytes: &[u8], path: &str) -> Result<(), EncodeErr> {
     let mut file = File::create(path).map_err(|_| EncodeErr::CreateFailed)?;
     file.write_all(bytes).map_err(|_| EncodeErr::WriteFailed)?;
 
@@ -0,0 +1,119 @@
+use crate::{
+    write::{ ops::OpMap, constants::Constants, cycles::{ self, Cycles }, data },
+    token::read::TokenRef,
+    parse::lex::TokenType::*,
+    error::asm::EncodeErr,
+};
+
+use std::fmt::Write;
+
+/// Bytes shown on each row of the listing.
+const BYTES_PER_ROW: usize = 8;
+
+/// Tokens from the same line of source.
+struct Row<'a> {
+    token: &'a TokenRef<'a>,
+    start: usize,
+    end: usize,
+    cycles: Option<Cycles>,
+}
+
+/// Writes the address, bytes and cycles of each line of source that is part of the output.
+pub fn build(
+    path: &str,
+    ast: &TokenRef,
+    op_map: &OpMap,
+    constants: &Constants,
+    bytes: &[u8],
+) -> Result<(), EncodeErr> {
+    let mut tokens = vec![];
+    cycles::flatten(ast, &mut tokens);
+
+    let mut rows: Vec<Row> = vec![];
+    let mut location = 0;
+
+    for token in tokens {
+        let (size, op_cycles) = match token.ty() {
+            Instruction => {
+                let opcode = op_map.get(token);
+                (opcode.len as usize, Some(opcode.cycles))
+            }
+
+            Lit => (token.leaf().value().as_bytes().map_or(0, |b| b.len()), None),
+
+            Identifier => {
+                let value = constants.get(token.value().as_str().unwrap()).and_then(|c| c.as_value().ok());
+                (value.and_then(|v| v.as_bytes().ok()).map_or(0, |b| b.len()), None)
+            }
+
+            Directive if matches!(token.first().ty(), Include|Rle|Lz) => {
+                (constants.included(token.first()).map_or(0, <[u8]>::len), None)
+            }
+
+            Directive if matches!(token.first().ty(), DataB|DataW|Fill) => (data::size(token.first()).unwrap_or(0), None),
+            Directive if token.first().ty() == Align => (data::padding(token.first(), location).unwrap_or(0), None),
+
+            Marker => {
+                if matches!(token.first().ty(), NamedMark|AnonMark) {
+                    location = token.first().leaf().value().as_num().unwrap();
+                }
+
+                (0, None)
+            }
+
+            _ => continue,
+        };
+
+        let start = location;
+        location += size;
+
+        if let Some(row) = rows.last_mut() {
+            let same_line = row.token.file() == token.file()
+                && row.token.line_number() == token.line_number()
+                && row.end == start;
+
+            if same_line {
+                row.end = location;
+
+                if let Some(op_cycles) = op_cycles {
+                    *row.cycles.get_or_insert_with(Cycles::default) += op_cycles;
+                }
+
+                continue;
+            }
+        }
+
+        rows.push(Row{ token, start, end: location, cycles: op_cycles.map(Cycles::from) });
+    }
+
+    std::fs::write(path, format(&rows, bytes)).map_err(|_| EncodeErr::ListingFailed)
+}
+
+fn format(rows: &[Row], bytes: &[u8]) -> String {
+    let mut listing = String::new();
+
+    for row in rows {
+        let data = bytes.get(row.start..row.end).unwrap_or_default();
+        let mut chunks = data.chunks(BYTES_PER_ROW);
+
+        let first = chunks.next().map(hex).unwrap_or_default();
+        let cycles = row.cycles.map(|c| c.to_string()).unwrap_or_default();
+        let source = format!("{}:{}", row.token.file(), row.token.line_number());
+
+        let line = format!("{:04X}  {first:<24}{cycles:<8}{source:<24}  {}",
+            row.start, row.token.line().trim());
+
+        let _ = writeln!(listing, "{}", line.trim_end());
+
+        for (i, chunk) in chunks.enumerate() {
+            let address = row.start + (i + 1) * BYTES_PER_ROW;
+            let _ = writeln!(listing, "{address:04X}  {}", hex(chunk).trim_end());
+        }
+    }
+
+    listing
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X} ")).collect()
+}
@@ -16,3 +16,24 @@ pub mod ops;
 /// Write the output of the assembler.
 pub mod encode;
 
+/// Writes the source over a base ROM, and makes patches of it.
+pub mod patch;
+
+/// Lists the files used to build the output, for make.
+pub mod deps;
+
+/// Writes the data of the `#byte`, `#word` and `#fill` directives, and the padding of `#align`.
+pub mod data;
+
+/// Compresses included data.
+pub mod compress;
+
+/// Counts the machine cycles of instructions.
+pub mod cycles;
+
+/// Lists the address, bytes and cycles of each line of source.
+pub mod listing;
+
+
+/// Evaluates the assertions and the messages of the source.
+pub mod checks;
@@ -1,6 +1,6 @@
 
 use crate::{
-    write::instructions,
+    write::instructions::{ self, Variant },
     parse::lex::TokenType::{self, *},
     token::{ read::TokenRef, Value },
     error::asm::{ AsmErr, OpMsg::{self, *} },
@@ -57,7 +57,7 @@ impl OpMap {
 
                     if map.insert(token.index(), opcode.unwrap()).is_some() {
                         //TODO fix: this happens when using multiple macros.
-                        bug!("OpMap entry was written twice");
+                        errors.push(bug!(OpMsg, token.into()));
                     }
                 }
 
@@ -73,6 +73,9 @@ pub struct OpCode {
     pub cb: bool,
     pub code: u8,
     pub len: u8,
+    /// Machine cycles when the condition is met, and when it is not.
+    /// Both are the same for unconditional instructions.
+    pub cycles: (u8, u8),
 }
 
 impl OpCode {
@@ -80,17 +83,17 @@ impl OpCode {
     pub fn get_opcode(
         instruction: &TokenRef, 
         cb: bool, 
-        ops: Vec<(u8, u8, Vec<Arg>)>
+        ops: Vec<Variant>
     ) -> Option<Self> {
         let instr_children = instruction.children()[1..].iter()
             .map(|c| c.first())
             .collect::<Vec<_>>();
 
         for op in ops {
-            let (len, code, op_args) = op;
+            let (len, code, cycles, op_args) = op;
 
             if Self::cmp_args(&instr_children, &op_args) {
-                let opcode = Self{ cb, code, len };
+                let opcode = Self{ cb, code, len, cycles };
                 return Some(opcode);     
             }
         }
@@ -165,7 +168,7 @@ impl Constant {
                 }
             }
 
-            BitN(b) => token.value().as_u8().map_or(false, |value|
+            BitN(b) => token.value().as_u8().is_ok_and(|value|
                 (token.ty() == LitDec) && (value == *b))
         }
     }
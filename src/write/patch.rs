@@ -0,0 +1,165 @@
+use crate::{
+    write::encode::Gap,
+    token::read::TokenRef,
+    error::asm::{ AsmErr, PatchMsg, EncodeErr },
+};
+
+/// Bytes written by the source, from `start` to `end` excluded.
+/// `marker` is the marker the block starts at, `None` for the block at the start of the image.
+#[derive(Debug, Copy, Clone)]
+pub struct Block<'a> {
+    pub marker: Option<&'a TokenRef<'a>>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Blocks between the gaps of an image of `len` bytes.
+pub fn blocks<'a>(gaps: &[Gap<'a>], len: usize) -> Vec<Block<'a>> {
+    let mut blocks = vec![];
+    let mut block = Block{ marker: None, start: 0, end: len };
+
+    for gap in gaps {
+        blocks.push(Block{ end: gap.start, ..block });
+        block = Block{ marker: Some(gap.marker), start: gap.end, end: len };
+    }
+
+    blocks.push(block);
+    blocks.retain(|block| block.start < block.end);
+    blocks
+}
+
+/// The base ROM with the blocks of `bytes` written over it.
+/// A base shorter than the image is padded with `0xFF`.
+pub fn over(base: &[u8], bytes: &[u8], blocks: &[Block]) -> Vec<u8> {
+    let mut rom = base.to_vec();
+
+    if rom.len() < bytes.len() {
+        rom.resize(bytes.len(), 0xFF);
+    }
+
+    for block in blocks {
+        rom[block.start..block.end].copy_from_slice(&bytes[block.start..block.end]);
+    }
+
+    rom
+}
+
+/// Blocks after a marker that leave the base ROM as it was.
+pub fn unchanged<'a>(base: &[u8], bytes: &[u8], blocks: &[Block<'a>]) -> Vec<AsmErr<'a, PatchMsg>> {
+    blocks.iter()
+        .filter(|block| base.get(block.start..block.end) == bytes.get(block.start..block.end))
+        .filter_map(|block| block.marker)
+        .map(|marker| err!(PatchMsg, PatchMsg::Unchanged, marker.into()))
+        .collect()
+}
+
+/// Records of the runs of bytes that differ from the base, of at most 64KB each.
+/// A target shorter than the base is truncated after the end of the patch.
+pub fn ips(base: &[u8], target: &[u8]) -> Result<Vec<u8>, EncodeErr> {
+    // Offsets are written on 3 bytes.
+    if target.len() > 0x0100_0000 {
+        return Err(EncodeErr::TooLargeForIps);
+    }
+
+    let changed = |i: usize| base.get(i) != Some(&target[i]);
+    let mut patch = b"PATCH".to_vec();
+    let mut offset = 0;
+
+    while offset < target.len() {
+        if !changed(offset) {
+            offset += 1;
+            continue;
+        }
+
+        // A record at the offset spelling "EOF" would end the patch, it starts a byte before.
+        let start = if offset == 0x454F46 { offset - 1 } else { offset };
+        let mut end = offset;
+
+        while end < target.len() && end - start < 0xFFFF && changed(end) {
+            end += 1;
+        }
+
+        patch.extend_from_slice(&(start as u32).to_be_bytes()[1..]);
+        patch.extend_from_slice(&((end - start) as u16).to_be_bytes());
+        patch.extend_from_slice(&target[start..end]);
+        offset = end;
+    }
+
+    patch.extend_from_slice(b"EOF");
+
+    if target.len() < base.len() {
+        patch.extend_from_slice(&(target.len() as u32).to_be_bytes()[1..]);
+    }
+
+    Ok(patch)
+}
+
+/// Reads the bytes of the base that are unchanged at the same offset, and writes the others.
+pub fn bps(base: &[u8], target: &[u8]) -> Vec<u8> {
+    const SOURCE_READ: usize = 0;
+    const TARGET_READ: usize = 1;
+
+    let same = |i: usize| base.get(i) == Some(&target[i]);
+    let mut patch = b"BPS1".to_vec();
+
+    number(&mut patch, base.len());
+    number(&mut patch, target.len());
+    // No metadata.
+    number(&mut patch, 0);
+
+    let mut offset = 0;
+
+    while offset < target.len() {
+        let read = same(offset);
+        let mut end = offset;
+
+        while end < target.len() && same(end) == read {
+            end += 1;
+        }
+
+        let action = if read { SOURCE_READ } else { TARGET_READ };
+        number(&mut patch, (end - offset - 1) << 2 | action);
+
+        if !read {
+            patch.extend_from_slice(&target[offset..end]);
+        }
+
+        offset = end;
+    }
+
+    patch.extend_from_slice(&crc32(base).to_le_bytes());
+    patch.extend_from_slice(&crc32(target).to_le_bytes());
+    patch.extend_from_slice(&crc32(&patch).to_le_bytes());
+    patch
+}
+
+/// Variable length number of BPS, 7 bits per byte with the last one flagged.
+fn number(patch: &mut Vec<u8>, mut n: usize) {
+    loop {
+        let bits = (n & 0x7F) as u8;
+        n >>= 7;
+
+        if n == 0 {
+            patch.push(0x80 | bits);
+            return;
+        }
+
+        patch.push(bits);
+        n -= 1;
+    }
+}
+
+/// CRC-32 of zip and PNG.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 }else{ crc >> 1 };
+        }
+    }
+
+    !crc
+}
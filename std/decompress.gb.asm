@@ -0,0 +1,74 @@
+;Decompression routines for the #rle and #lz directives.
+;Both take the compressed data in HL and the destination in DE.
+;On return, HL points after the compressed data and DE after the output.
+;Registers A, B and C are not preserved.
+
+;--------------------------------------------------
+
+:RleDecompress
+    ldi a (hl)              ;Packet header
+    and a
+    ret Z                   ;0: end of data
+    bit 7 a
+    jp NZ RleDecompressRun
+    ld b a                  ;&01-&7F: copy the next N bytes
+:RleDecompressCopy
+    ldi a (hl)
+    ld (de) a
+    inc de
+    dec b
+    jp NZ RleDecompressCopy
+    jp RleDecompress
+
+:RleDecompressRun
+    and &7F                 ;&80-&FF: repeat the next byte (N & &7F) + 2 times
+    add 2
+    ld b a
+    ldi a (hl)
+:RleDecompressFill
+    ld (de) a
+    inc de
+    dec b
+    jp NZ RleDecompressFill
+    jp RleDecompress
+
+;--------------------------------------------------
+
+:LzDecompress
+    ldi a (hl)              ;Packet header
+    and a
+    ret Z                   ;0: end of data
+    bit 7 a
+    jp NZ LzDecompressMatch
+    ld b a                  ;&01-&7F: copy the next N bytes
+:LzDecompressCopy
+    ldi a (hl)
+    ld (de) a
+    inc de
+    dec b
+    jp NZ LzDecompressCopy
+    jp LzDecompress
+
+:LzDecompressMatch
+    and &7F                 ;&80-&FF: copy (N & &7F) + 3 bytes already written,
+    add 3                   ;the distance back is in the next 2 bytes
+    ld b a
+    ldi a (hl)
+    ld c a                  ;Distance, low byte
+    ldi a (hl)              ;Distance, high byte
+    push hl
+        ld h a
+        ld a e              ;HL = DE - distance
+        sub c
+        ld l a
+        ld a d
+        sbc h
+        ld h a
+:LzDecompressRepeat
+        ldi a (hl)
+        ld (de) a
+        inc de
+        dec b
+        jp NZ LzDecompressRepeat
+    pop hl
+    jp LzDecompress
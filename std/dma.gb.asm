@@ -0,0 +1,35 @@
+;OAM DMA transfer.
+;The CPU can only read HRAM during the transfer, so the routine runs from there.
+;Call DmaInit once, then start a transfer with the high byte of the source in A:
+;    ld a &C1            ;Copies &C100->&C19F to OAM
+;    call DMA_TRANSFER
+
+#import "std:hardware.gb.asm"
+
+#dw DMA_TRANSFER _HRAM
+#db DMA_ROUTINE_LEN DmaRoutineEnd - DmaRoutine
+
+;--------------------------------------------------
+
+;Copies the transfer routine to HRAM.
+;Registers A, B, DE and HL are not preserved.
+:DmaInit
+    ld hl DmaRoutine
+    ld de DMA_TRANSFER
+    ld b DMA_ROUTINE_LEN
+:DmaInitCopy
+    ldi a (hl)
+    ld (de) a
+    inc de
+    dec b
+    jp NZ DmaInitCopy
+    ret
+
+;Copied to HRAM by DmaInit, not to be called from ROM.
+:DmaRoutine
+    ldh (&46) a             ;rDMA: start the transfer
+    ld a 40                 ;Wait 160 cycles
+    dec a
+    &20 &FD                 ;jr NZ -3
+    ret
+:DmaRoutineEnd
@@ -0,0 +1,36 @@
+;Joypad reading.
+
+#import "std:hardware.gb.asm"
+
+;--------------------------------------------------
+
+;Reads the state of the joypad into A, 1 for pressed.
+;The bits match the PADF_ constants: down, up, left, right, start, select, B, A.
+;Register B is not preserved.
+:ReadJoypad
+    ld a P1F_GET_DPAD
+    ldh (&00) a             ;rP1
+    ldh a (&00)             ;Read twice to let the value settle
+    ldh a (&00)
+    cpl                     ;0 is pressed
+    and &0F
+    swap a
+    ld b a
+
+    ld a P1F_GET_BTN
+    ldh (&00) a
+    ldh a (&00)             ;Buttons need more reads to settle
+    ldh a (&00)
+    ldh a (&00)
+    ldh a (&00)
+    ldh a (&00)
+    ldh a (&00)
+    cpl
+    and &0F
+    or b
+    ld b a
+
+    ld a P1F_GET_NONE
+    ldh (&00) a
+    ld a b
+    ret
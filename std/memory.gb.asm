@@ -0,0 +1,34 @@
+;Memory routines.
+
+;--------------------------------------------------
+
+;Copies BC bytes from HL to DE.
+;On return, HL and DE point after the copied bytes.
+;Registers A, B and C are not preserved.
+:MemCopy
+    ld a b
+    or c
+    ret Z
+:MemCopyLoop
+    ldi a (hl)
+    ld (de) a
+    inc de
+    dec bc
+    ld a b
+    or c
+    jp NZ MemCopyLoop
+    ret
+
+;Fills BC bytes at HL with the value in A.
+;On return, HL points after the filled bytes.
+;Registers A, B, C and D are not preserved.
+:MemSet
+    ld d a
+:MemSetLoop
+    ld a b
+    or c
+    ret Z
+    ld a d
+    ldi (hl) a
+    dec bc
+    jp MemSetLoop